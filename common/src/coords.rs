@@ -0,0 +1,40 @@
+/// A (row, column) position on a grid.
+pub type Coords = (usize, usize);
+
+/// Adds a signed offset to an index.
+/// Returns `None` if the result would be negative or overflow.
+#[must_use]
+pub fn checked_add(index: usize, offset: isize) -> Option<usize> {
+	index.checked_add_signed(offset)
+}
+
+/// Checks if a position is on a grid of a given size.
+#[must_use]
+pub fn in_bounds((row, col): Coords, nb_rows: usize, nb_cols: usize) -> bool {
+	row < nb_rows && col < nb_cols
+}
+
+#[cfg(test)]
+mod test_coords {
+	use super::*;
+
+	#[test]
+	fn test_checked_add() {
+		assert_eq!(checked_add(3, 2), Some(5));
+		assert_eq!(checked_add(3, -3), Some(0));
+		assert_eq!(checked_add(0, -1), None);
+		assert_eq!(checked_add(3, -4), None);
+		assert_eq!(checked_add(usize::MAX, 0), Some(usize::MAX));
+		assert_eq!(checked_add(usize::MAX, 1), None);
+		assert_eq!(checked_add(usize::MAX - 1, isize::MAX), None);
+	}
+
+	#[test]
+	fn test_in_bounds() {
+		assert!(in_bounds((0, 0), 2, 3));
+		assert!(in_bounds((1, 2), 2, 3));
+		assert!(!in_bounds((2, 0), 2, 3));
+		assert!(!in_bounds((0, 3), 2, 3));
+		assert!(!in_bounds((0, 0), 0, 0));
+	}
+}
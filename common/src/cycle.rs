@@ -0,0 +1,120 @@
+/// Finds where a sequence of states starts repeating, using Brent's algorithm.
+/// The sequence is `start`, then `start` advanced by `step` once, twice, and so on, and must repeat eventually.
+/// Returns the index of the first state in the cycle, and the cycle length.
+/// Only a couple of states are kept at a time, however long the sequence runs before cycling.
+/// `step` advances a state in place, so states are only cloned a logarithmic number of times.
+/// # Panics
+/// If the sequence runs too long to count before repeating.
+#[must_use]
+pub fn detect_cycle<S: Clone + Eq>(step: impl FnMut(&mut S), start: S) -> (usize, usize) {
+	detect_cycle_within(step, start, usize::MAX).expect("Sequence never repeats")
+}
+
+/// Like `detect_cycle`, but gives up unless the first repeat comes within `max_steps` steps,
+/// i.e. the offset plus the cycle length is at most `max_steps`.
+/// Takes at most about `3 * max_steps` steps before giving up.
+#[must_use]
+pub fn detect_cycle_within<S: Clone + Eq>(
+	mut step: impl FnMut(&mut S),
+	start: S,
+	max_steps: usize,
+) -> Option<(usize, usize)> {
+	// Find the length: race a hare ahead, moving the tortoise up to it at every power of 2.
+	// The hare meets the tortoise within 3 × (offset + length) steps.
+	let hare_limit = max_steps.saturating_mul(3);
+	let mut hare_index = 1;
+	let mut power = 1;
+	let mut length = 1;
+	let mut tortoise = start.clone();
+	let mut hare = start.clone();
+	step(&mut hare);
+	while tortoise != hare {
+		if hare_index >= hare_limit {
+			return None;
+		}
+		if power == length {
+			tortoise = hare.clone();
+			power *= 2;
+			length = 0;
+		}
+		step(&mut hare);
+		hare_index += 1;
+		length += 1;
+	}
+	if length > max_steps {
+		return None;
+	}
+
+	// Find the offset: with the hare one cycle ahead, both meet where the cycle starts.
+	let mut tortoise = start.clone();
+	let mut hare = start;
+	for _ in 0..length {
+		step(&mut hare);
+	}
+	let mut offset = 0;
+	while tortoise != hare {
+		if offset + length >= max_steps {
+			return None;
+		}
+		step(&mut tortoise);
+		step(&mut hare);
+		offset += 1;
+	}
+
+	Some((offset, length))
+}
+
+#[cfg(test)]
+mod test_detect_cycle {
+	use super::*;
+
+	#[test]
+	fn test_tail_then_cycle() {
+		// a, b, c, d, c, d, c, d, ...
+		let step = |state: &mut char| {
+			*state = match state {
+				'a' => 'b',
+				'b' | 'd' => 'c',
+				_ => 'd',
+			};
+		};
+		assert_eq!(detect_cycle(step, 'a'), (2, 2));
+		assert_eq!(detect_cycle(step, 'c'), (0, 2));
+	}
+
+	#[test]
+	fn test_fixed_point() {
+		assert_eq!(
+			detect_cycle(|n: &mut u32| *n = (*n / 2).max(1), 100),
+			(6, 1)
+		);
+	}
+
+	#[test]
+	fn test_long_cycle() {
+		// 0, 1, 2, ..., 9, then 3, 4, ..., 9 forever.
+		let step = |n: &mut u32| *n = if *n == 9 { 3 } else { *n + 1 };
+		assert_eq!(detect_cycle(step, 0), (3, 7));
+	}
+
+	#[test]
+	fn test_within() {
+		// 0, 1, 2, ..., 9, then 3, 4, ..., 9 forever: first repeat after 10 steps.
+		let step = |n: &mut u32| *n = if *n == 9 { 3 } else { *n + 1 };
+		assert_eq!(detect_cycle_within(step, 0, 10), Some((3, 7)));
+		assert_eq!(detect_cycle_within(step, 0, 1000), Some((3, 7)));
+		assert_eq!(detect_cycle_within(step, 0, 9), None);
+		assert_eq!(detect_cycle_within(step, 0, 0), None);
+	}
+
+	#[test]
+	fn test_within_gives_up_early() {
+		let mut nb_steps = 0;
+		let counting_step = |n: &mut u64| {
+			nb_steps += 1;
+			*n += 1;
+		};
+		assert_eq!(detect_cycle_within(counting_step, 0, 100), None);
+		assert!(nb_steps <= 300);
+	}
+}
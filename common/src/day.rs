@@ -0,0 +1,59 @@
+/// A day's puzzle, with both parts solved from the raw input.
+/// Answers come back as strings so every day can be run and printed the same way.
+/// Only days 1, 6 and 9 implement it: they are the library days the runner can dispatch to.
+pub trait Day {
+	/// Solves part 1.
+	fn part1(input: &str) -> String;
+	/// Solves part 2.
+	fn part2(input: &str) -> String;
+}
+
+/// Both parts of a day as plain functions, so different days fit in one table.
+pub type Solver = (fn(&str) -> String, fn(&str) -> String);
+
+/// The solver for a day, for the runner to dispatch to.
+#[must_use]
+pub fn solver<D: Day>() -> Solver {
+	(D::part1, D::part2)
+}
+
+#[cfg(test)]
+mod test_solver {
+	use super::*;
+
+	struct Length;
+	impl Day for Length {
+		fn part1(input: &str) -> String {
+			input.len().to_string()
+		}
+		fn part2(input: &str) -> String {
+			input.lines().count().to_string()
+		}
+	}
+
+	struct Shout;
+	impl Day for Shout {
+		fn part1(input: &str) -> String {
+			input.to_uppercase()
+		}
+		fn part2(input: &str) -> String {
+			format!("{input}!")
+		}
+	}
+
+	#[test]
+	fn test_solver() {
+		let solvers = [solver::<Length>(), solver::<Shout>()];
+		let answers: Vec<(String, String)> = solvers
+			.iter()
+			.map(|(part1, part2)| (part1("ab\ncd"), part2("ab\ncd")))
+			.collect();
+		assert_eq!(
+			answers,
+			vec![
+				("5".to_string(), "2".to_string()),
+				("AB\nCD".to_string(), "ab\ncd!".to_string())
+			]
+		);
+	}
+}
@@ -0,0 +1,126 @@
+use std::{
+	cmp::{Ordering, Reverse},
+	collections::{BinaryHeap, HashSet},
+	hash::Hash,
+	ops::Add,
+};
+
+/// A state waiting to be expanded, ordered by cost only.
+struct Queued<S, C> {
+	cost: C,
+	state: S,
+}
+
+impl<S, C: Ord> PartialEq for Queued<S, C> {
+	fn eq(&self, other: &Self) -> bool {
+		self.cost == other.cost
+	}
+}
+
+impl<S, C: Ord> Eq for Queued<S, C> {}
+
+impl<S, C: Ord> PartialOrd for Queued<S, C> {
+	fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+		Some(self.cmp(other))
+	}
+}
+
+impl<S, C: Ord> Ord for Queued<S, C> {
+	fn cmp(&self, other: &Self) -> Ordering {
+		self.cost.cmp(&other.cost)
+	}
+}
+
+/// Finds the least cost from any of the start states to a goal state, if one can be reached.
+/// `neighbours` gives the states one step away from a state, with the cost of that step.
+/// Costs start at `C::default()` and must never go down along a step.
+/// Only the cost is returned: searches that need routes, heuristics or statistics should keep their own loop.
+pub fn dijkstra<S, C, I>(
+	start_states: impl IntoIterator<Item = S>,
+	mut neighbours: impl FnMut(&S) -> I,
+	mut is_goal: impl FnMut(&S) -> bool,
+) -> Option<C>
+where
+	S: Clone + Eq + Hash,
+	C: Copy + Default + Ord + Add<Output = C>,
+	I: IntoIterator<Item = (S, C)>,
+{
+	let mut open: BinaryHeap<Reverse<Queued<S, C>>> = start_states
+		.into_iter()
+		.map(|state| {
+			Reverse(Queued {
+				cost: C::default(),
+				state,
+			})
+		})
+		.collect();
+	let mut closed = HashSet::new();
+
+	while let Some(Reverse(Queued { cost, state })) = open.pop() {
+		// Already reached more cheaply
+		if closed.contains(&state) {
+			continue;
+		}
+		if is_goal(&state) {
+			return Some(cost);
+		}
+		for (neighbour, step_cost) in neighbours(&state) {
+			if !closed.contains(&neighbour) {
+				open.push(Reverse(Queued {
+					cost: cost + step_cost,
+					state: neighbour,
+				}));
+			}
+		}
+		closed.insert(state);
+	}
+
+	None
+}
+
+#[cfg(test)]
+mod test_dijkstra {
+	use super::*;
+
+	/// a -1-> b -2-> c -1-> d, with a costlier shortcut a -4-> c, and e off on its own.
+	fn neighbours(node: char) -> Vec<(char, u32)> {
+		match node {
+			'a' => vec![('b', 1), ('c', 4)],
+			'b' => vec![('c', 2), ('a', 1)],
+			'c' => vec![('d', 1)],
+			_ => vec![],
+		}
+	}
+
+	#[test]
+	fn test_tiny_graph() {
+		assert_eq!(
+			dijkstra(['a'], |&node| neighbours(node), |&node| node == 'd'),
+			Some(4)
+		);
+		assert_eq!(
+			dijkstra(['a'], |&node| neighbours(node), |&node| node == 'a'),
+			Some(0)
+		);
+		assert_eq!(
+			dijkstra(['b', 'c'], |&node| neighbours(node), |&node| node == 'd'),
+			Some(1)
+		);
+	}
+
+	#[test]
+	fn test_unreachable() {
+		assert_eq!(
+			dijkstra(['a'], |&node| neighbours(node), |&node| node == 'e'),
+			None
+		);
+		assert_eq!(
+			dijkstra(
+				Vec::<char>::new(),
+				|&node| neighbours(node),
+				|&node| node == 'a'
+			),
+			None
+		);
+	}
+}
@@ -0,0 +1,76 @@
+use self::Direction::{Down, Left, Right, Up};
+
+/// One of the four directions along a grid's rows and columns.
+#[derive(Debug, Clone, Copy, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub enum Direction {
+	Up,
+	Down,
+	Left,
+	Right,
+}
+
+impl Direction {
+	pub const ALL: [Direction; 4] = [Up, Down, Left, Right];
+
+	/// Gets the (row, column) change from one step in this direction.
+	#[must_use]
+	pub fn delta(self) -> (isize, isize) {
+		match self {
+			Up => (-1, 0),
+			Down => (1, 0),
+			Left => (0, -1),
+			Right => (0, 1),
+		}
+	}
+
+	/// Gets the direction going straight back.
+	#[must_use]
+	pub fn opposite(self) -> Direction {
+		match self {
+			Up => Down,
+			Down => Up,
+			Left => Right,
+			Right => Left,
+		}
+	}
+
+	/// Gets the two directions at a right angle to this one.
+	#[must_use]
+	pub fn turns(self) -> [Direction; 2] {
+		match self {
+			Up | Down => [Left, Right],
+			Left | Right => [Up, Down],
+		}
+	}
+}
+
+#[cfg(test)]
+mod test_direction {
+	use super::*;
+
+	#[test]
+	fn test_delta() {
+		assert_eq!(Up.delta(), (-1, 0));
+		assert_eq!(Down.delta(), (1, 0));
+		assert_eq!(Left.delta(), (0, -1));
+		assert_eq!(Right.delta(), (0, 1));
+		for direction in Direction::ALL {
+			let (row, col) = direction.delta();
+			assert_eq!(direction.opposite().delta(), (-row, -col));
+		}
+	}
+
+	#[test]
+	fn test_turns() {
+		assert_eq!(Up.turns(), [Left, Right]);
+		assert_eq!(Down.turns(), [Left, Right]);
+		assert_eq!(Left.turns(), [Up, Down]);
+		assert_eq!(Right.turns(), [Up, Down]);
+		for direction in Direction::ALL {
+			for turn in direction.turns() {
+				assert_ne!(turn, direction);
+				assert_ne!(turn, direction.opposite());
+			}
+		}
+	}
+}
@@ -0,0 +1,77 @@
+use std::fmt::{Display, Write};
+
+/// Prints both answers, as `{"part1": ..., "part2": ...}` if `json`, otherwise one labelled line each.
+pub fn emit(part1: (&str, impl Display), part2: (&str, impl Display), json: bool) {
+	print!("{}", format_answers(part1, part2, json));
+}
+
+/// Formats both answers the way `emit` prints them.
+#[must_use]
+pub fn format_answers(
+	(label1, part1): (&str, impl Display),
+	(label2, part2): (&str, impl Display),
+	json: bool,
+) -> String {
+	if json {
+		format!(
+			"{{\"part1\": {}, \"part2\": {}}}\n",
+			json_value(&part1.to_string()),
+			json_value(&part2.to_string())
+		)
+	} else {
+		format!("{label1}: {part1}\n{label2}: {part2}\n")
+	}
+}
+
+/// Writes an answer as a JSON number if it is an integer, or as a JSON string otherwise.
+#[must_use]
+fn json_value(answer: &str) -> String {
+	if answer.parse::<i128>().is_ok() {
+		return answer.to_string();
+	}
+	let mut quoted = String::from("\"");
+	for ch in answer.chars() {
+		match ch {
+			'"' => quoted.push_str("\\\""),
+			'\\' => quoted.push_str("\\\\"),
+			'\n' => quoted.push_str("\\n"),
+			ch if ch.is_control() => {
+				_ = write!(quoted, "\\u{:04x}", u32::from(ch));
+			}
+			ch => quoted.push(ch),
+		}
+	}
+	quoted.push('"');
+	quoted
+}
+
+#[cfg(test)]
+mod test_emit {
+	use super::*;
+
+	#[test]
+	fn test_human() {
+		assert_eq!(
+			format_answers(("Score", 13), ("Cards", 30), false),
+			"Score: 13\nCards: 30\n"
+		);
+	}
+
+	#[test]
+	fn test_json() {
+		assert_eq!(
+			format_answers(("Score", 13), ("Cards", -30), true),
+			"{\"part1\": 13, \"part2\": -30}\n"
+		);
+	}
+
+	#[test]
+	fn test_json_strings() {
+		assert_eq!(
+			format_answers(("Code", "AB\"C"), ("Path", "a\\b\n"), true),
+			"{\"part1\": \"AB\\\"C\", \"part2\": \"a\\\\b\\n\"}\n"
+		);
+		assert_eq!(json_value("1.5"), "\"1.5\"");
+		assert_eq!(json_value("\u{7}"), "\"\\u0007\"");
+	}
+}
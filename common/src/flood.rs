@@ -0,0 +1,53 @@
+use std::{collections::HashSet, hash::Hash};
+
+/// Finds all nodes reachable from `start`, including itself, by following `neighbours` from each node found.
+pub fn flood_fill<N, I>(start: N, mut neighbours: impl FnMut(&N) -> I) -> HashSet<N>
+where
+	N: Clone + Eq + Hash,
+	I: IntoIterator<Item = N>,
+{
+	let mut filled = HashSet::new();
+	let mut border = vec![start];
+	while let Some(node) = border.pop() {
+		// Don't re-visit same node.
+		if filled.contains(&node) {
+			continue;
+		}
+		border.extend(neighbours(&node));
+		filled.insert(node);
+	}
+	filled
+}
+
+#[cfg(test)]
+mod test_flood_fill {
+	use super::*;
+	use crate::Grid;
+
+	/// Open tiles up, down, left, and right of a position.
+	fn open_neighbours(grid: &Grid<char>, (row, col): (usize, usize)) -> Vec<(usize, usize)> {
+		grid.neighbours4(row, col)
+			.filter(|&(row, col)| grid[row][col] == '.')
+			.collect()
+	}
+
+	#[test]
+	fn test_open_region() {
+		let grid = Grid::from(vec![vec!['.'; 3]; 2]);
+		let filled = flood_fill((0, 0), |&node| open_neighbours(&grid, node));
+		assert_eq!(filled.len(), 6);
+	}
+
+	#[test]
+	fn test_walled_off_region() {
+		let grid = Grid::from(vec![
+			"..#..".chars().collect(),
+			"..#..".chars().collect(),
+			"###..".chars().collect(),
+		]);
+		let filled = flood_fill((0, 0), |&node| open_neighbours(&grid, node));
+		assert_eq!(filled, HashSet::from([(0, 0), (0, 1), (1, 0), (1, 1)]));
+		let filled = flood_fill((2, 4), |&node| open_neighbours(&grid, node));
+		assert_eq!(filled.len(), 6);
+	}
+}
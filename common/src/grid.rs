@@ -0,0 +1,332 @@
+use std::{
+	error, fmt,
+	ops::{Index, IndexMut},
+};
+
+use crate::{checked_add, in_bounds, Coords, Direction};
+
+/// A rectangular 2D array of tiles, indexed by row then column.
+#[derive(Debug, Clone, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct Grid<T> {
+	nb_rows: usize,
+	nb_cols: usize,
+	tiles: Vec<Vec<T>>,
+}
+
+impl<T> Grid<T> {
+	/// Builds a grid from rows of tiles.
+	/// # Panics
+	/// If there are no tiles, or rows have different lengths.
+	#[must_use]
+	pub fn from(tiles: Vec<Vec<T>>) -> Self {
+		Self::try_from_rows(tiles).unwrap_or_else(|error| panic!("{error}"))
+	}
+
+	/// Builds a grid from rows of tiles, checking that there are some and they all have the same length.
+	/// # Errors
+	/// If there are no tiles, or rows have different lengths.
+	pub fn try_from_rows(tiles: Vec<Vec<T>>) -> Result<Self, GridError> {
+		let nb_cols = tiles.first().ok_or(GridError::Empty)?.len();
+		if nb_cols == 0 {
+			return Err(GridError::Empty);
+		}
+		if let Some((row, tiles_row)) = tiles
+			.iter()
+			.enumerate()
+			.find(|(_, tiles_row)| tiles_row.len() != nb_cols)
+		{
+			return Err(GridError::Ragged {
+				row,
+				expected: nb_cols,
+				found: tiles_row.len(),
+			});
+		}
+		Ok(Self {
+			nb_rows: tiles.len(),
+			nb_cols,
+			tiles,
+		})
+	}
+
+	/// Gets the number of rows.
+	#[must_use]
+	pub fn rows(&self) -> usize {
+		self.nb_rows
+	}
+
+	/// Gets the number of columns.
+	#[must_use]
+	pub fn cols(&self) -> usize {
+		self.nb_cols
+	}
+
+	/// Gets the tile at a position, if it's on the grid.
+	#[must_use]
+	pub fn get(&self, row: usize, col: usize) -> Option<&T> {
+		self.tiles.get(row)?.get(col)
+	}
+
+	/// Gets the tile at a position mutably, if it's on the grid.
+	#[must_use]
+	pub fn get_mut(&mut self, row: usize, col: usize) -> Option<&mut T> {
+		self.tiles.get_mut(row)?.get_mut(col)
+	}
+
+	/// Gets the position one step from a position by a (row, column) change, if it's on the grid.
+	#[must_use]
+	pub fn step(
+		&self,
+		row: usize,
+		col: usize,
+		(row_delta, col_delta): (isize, isize),
+	) -> Option<Coords> {
+		let coords = (checked_add(row, row_delta)?, checked_add(col, col_delta)?);
+		in_bounds(coords, self.nb_rows, self.nb_cols).then_some(coords)
+	}
+
+	/// Lists the positions up, down, left, and right of a position that are on the grid.
+	pub fn neighbours4(&self, row: usize, col: usize) -> impl Iterator<Item = Coords> + '_ {
+		Direction::ALL
+			.into_iter()
+			.filter_map(move |direction| self.step(row, col, direction.delta()))
+	}
+
+	/// Iterates over all tiles, row by row.
+	pub fn iter(&self) -> impl Iterator<Item = &T> {
+		self.tiles.iter().flatten()
+	}
+
+	/// Iterates mutably over all tiles, row by row.
+	pub fn iter_mut(&mut self) -> impl Iterator<Item = &mut T> {
+		self.tiles.iter_mut().flatten()
+	}
+
+	/// Iterates over the rows.
+	pub fn iter_rows(&self) -> impl Iterator<Item = &[T]> {
+		self.tiles.iter().map(Vec::as_slice)
+	}
+
+	/// Takes the rows of tiles back out of the grid.
+	#[must_use]
+	pub fn into_rows(self) -> Vec<Vec<T>> {
+		self.tiles
+	}
+}
+
+/// Reads a grid of chars, one row per line.
+/// # Errors
+/// If there are no tiles, or lines have different lengths.
+pub fn parse_char_grid(input: &str) -> Result<Grid<char>, GridError> {
+	parse_tile_grid(input, Some)
+}
+
+/// Reads a grid one row per line, turning each char into a tile with `read_tile`.
+/// # Errors
+/// If `read_tile` rejects a char, there are no tiles, or lines have different lengths.
+pub fn parse_tile_grid<T>(
+	input: &str,
+	read_tile: impl Fn(char) -> Option<T>,
+) -> Result<Grid<T>, GridError> {
+	let tiles = input
+		.lines()
+		.enumerate()
+		.map(|(row, line)| {
+			line.chars()
+				.enumerate()
+				.map(|(col, tile)| read_tile(tile).ok_or(GridError::InvalidTile { row, col, tile }))
+				.collect()
+		})
+		.collect::<Result<_, _>>()?;
+	Grid::try_from_rows(tiles)
+}
+
+/// Swaps rows and columns of rows of tiles.
+/// # Panics
+/// If rows have different lengths.
+#[must_use]
+pub fn transpose<T: Clone>(tiles: &[Vec<T>]) -> Vec<Vec<T>> {
+	let nb_cols = tiles.first().map_or(0, Vec::len);
+	assert!(tiles.iter().all(|row| row.len() == nb_cols), "Ragged grid");
+	(0..nb_cols)
+		.map(|col| tiles.iter().map(|row| row[col].clone()).collect())
+		.collect()
+}
+
+/// Why rows of tiles don't make a grid.
+#[derive(Debug, Clone, Copy, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub enum GridError {
+	/// There are no rows at all, or the first row is empty.
+	Empty,
+	/// A char, at a row and column counting from 0, that isn't a tile.
+	InvalidTile { row: usize, col: usize, tile: char },
+	/// A row, counting from 0, doesn't have the same length as the first.
+	Ragged {
+		row: usize,
+		expected: usize,
+		found: usize,
+	},
+}
+
+impl fmt::Display for GridError {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		match self {
+			GridError::Empty => write!(f, "Empty grid"),
+			GridError::InvalidTile { row, col, tile } => {
+				write!(f, "Invalid tile {tile:?} at row {row}, column {col}")
+			}
+			GridError::Ragged {
+				row,
+				expected,
+				found,
+			} => write!(
+				f,
+				"Ragged grid: row {row} has {found} tiles, expected {expected}"
+			),
+		}
+	}
+}
+
+impl error::Error for GridError {}
+
+impl<T> Index<usize> for Grid<T> {
+	type Output = [T];
+
+	fn index(&self, row: usize) -> &[T] {
+		&self.tiles[row]
+	}
+}
+
+impl<T> IndexMut<usize> for Grid<T> {
+	fn index_mut(&mut self, row: usize) -> &mut [T] {
+		&mut self.tiles[row]
+	}
+}
+
+impl<T: fmt::Display> fmt::Display for Grid<T> {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		for row in &self.tiles {
+			for tile in row {
+				write!(f, "{tile}")?;
+			}
+			writeln!(f)?;
+		}
+		Ok(())
+	}
+}
+
+#[cfg(test)]
+mod test_grid {
+	use super::*;
+
+	fn get_test_grid() -> Grid<u8> {
+		Grid::from(vec![vec![1, 2, 3], vec![4, 5, 6]])
+	}
+
+	#[test]
+	fn test_size() {
+		let grid = get_test_grid();
+		assert_eq!((grid.rows(), grid.cols()), (2, 3));
+	}
+
+	#[test]
+	#[should_panic(expected = "Ragged grid")]
+	fn test_ragged() {
+		let _ = Grid::from(vec![vec![1, 2], vec![3]]);
+	}
+
+	#[test]
+	fn test_get() {
+		let grid = get_test_grid();
+		assert_eq!(grid.get(1, 2), Some(&6));
+		assert_eq!(grid[0][1], 2);
+		assert_eq!(grid.get(2, 0), None);
+		assert_eq!(grid.get(0, 3), None);
+	}
+
+	#[test]
+	fn test_step() {
+		let grid = get_test_grid();
+		assert_eq!(grid.step(0, 0, (1, 1)), Some((1, 1)));
+		assert_eq!(grid.step(0, 0, (-1, 0)), None);
+		assert_eq!(grid.step(0, 0, (0, -1)), None);
+		assert_eq!(grid.step(1, 2, (1, 0)), None);
+		assert_eq!(grid.step(1, 2, (0, 1)), None);
+	}
+
+	#[test]
+	fn test_neighbours4() {
+		let grid = get_test_grid();
+		let corner: Vec<_> = grid.neighbours4(0, 0).collect();
+		assert_eq!(corner, vec![(1, 0), (0, 1)]);
+		let edge: Vec<_> = grid.neighbours4(1, 1).collect();
+		assert_eq!(edge, vec![(0, 1), (1, 0), (1, 2)]);
+	}
+
+	#[test]
+	fn test_display() {
+		assert_eq!(get_test_grid().to_string(), "123\n456\n");
+	}
+
+	#[test]
+	fn test_transpose() {
+		let tiles = vec![vec![1, 2, 3], vec![4, 5, 6]];
+		assert_eq!(transpose(&tiles), vec![vec![1, 4], vec![2, 5], vec![3, 6]]);
+		assert_eq!(transpose(&transpose(&tiles)), tiles);
+		assert_eq!(transpose::<u8>(&[]), Vec::<Vec<u8>>::new());
+	}
+
+	#[test]
+	#[should_panic(expected = "Ragged grid")]
+	fn test_transpose_ragged() {
+		let _ = transpose(&[vec![1, 2], vec![3]]);
+	}
+
+	#[test]
+	fn test_parse_char_grid() {
+		let grid = parse_char_grid("#.\n.#\n..\n").expect("Rectangular");
+		assert_eq!((grid.rows(), grid.cols()), (3, 2));
+		assert_eq!(grid[1][1], '#');
+		assert_eq!(grid.to_string(), "#.\n.#\n..\n");
+	}
+
+	#[test]
+	fn test_parse_char_grid_ragged() {
+		let error = parse_char_grid("#.\n.#.\n..\n").unwrap_err();
+		assert_eq!(
+			error,
+			GridError::Ragged {
+				row: 1,
+				expected: 2,
+				found: 3
+			}
+		);
+		assert_eq!(
+			error.to_string(),
+			"Ragged grid: row 1 has 3 tiles, expected 2"
+		);
+	}
+
+	#[test]
+	fn test_parse_char_grid_empty() {
+		assert_eq!(parse_char_grid(""), Err(GridError::Empty));
+		assert_eq!(parse_char_grid("\n\n"), Err(GridError::Empty));
+	}
+
+	#[test]
+	fn test_parse_tile_grid() {
+		let read_tile = |tile: char| tile.to_digit(10);
+		let grid = parse_tile_grid("12\n34\n", read_tile).expect("Digits");
+		assert_eq!(grid.into_rows(), vec![vec![1, 2], vec![3, 4]]);
+
+		let error = parse_tile_grid("12\n3x\n", read_tile).unwrap_err();
+		assert_eq!(
+			error,
+			GridError::InvalidTile {
+				row: 1,
+				col: 1,
+				tile: 'x'
+			}
+		);
+		assert_eq!(error.to_string(), "Invalid tile 'x' at row 1, column 1");
+	}
+}
@@ -0,0 +1,32 @@
+use std::process::Command;
+
+/// Runs a day's binary with the given arguments and returns what it printed to stdout.
+/// Meant for integration tests, with the path from `env!("CARGO_BIN_EXE_<name>")`.
+/// # Panics
+/// If the binary can't be started, exits with an error, or prints something that isn't UTF-8.
+#[must_use]
+pub fn run_binary(exe: &str, args: &[&str]) -> String {
+	let output = Command::new(exe)
+		.args(args)
+		.output()
+		.expect("Failed to run binary");
+	assert!(
+		output.status.success(),
+		"{exe} failed: {}",
+		String::from_utf8_lossy(&output.stderr)
+	);
+	String::from_utf8(output.stdout).expect("Output is not UTF-8")
+}
+
+/// Runs a day's binary on a sample input from its crate directory, followed by some flags,
+/// and returns what it printed to stdout.
+/// Meant for integration tests, with `env!("CARGO_BIN_EXE_<name>")` and `env!("CARGO_MANIFEST_DIR")`.
+/// # Panics
+/// If the binary can't be started, exits with an error, or prints something that isn't UTF-8.
+#[must_use]
+pub fn run_sample(exe: &str, manifest_dir: &str, input_file: &str, flags: &[&str]) -> String {
+	let path = format!("{manifest_dir}/{input_file}");
+	let mut args = vec![path.as_str()];
+	args.extend(flags);
+	run_binary(exe, &args)
+}
@@ -0,0 +1,88 @@
+use std::{
+	env, fs,
+	io::{self, Read},
+};
+
+/// Reads the puzzle input from the file named by the first argument that isn't a `--flag`,
+/// or from stdin if there's none.
+/// # Errors
+/// If the file or stdin can't be read.
+pub fn read_input(args: impl IntoIterator<Item = String>) -> io::Result<String> {
+	read_input_or(args, io::stdin())
+}
+
+/// Reads the puzzle input from the file named by the first argument that isn't a `--flag`,
+/// or from `fallback` if there's none.
+fn read_input_or(
+	args: impl IntoIterator<Item = String>,
+	mut fallback: impl Read,
+) -> io::Result<String> {
+	if let Some(path) = args.into_iter().find(|arg| !arg.starts_with("--")) {
+		return fs::read_to_string(path);
+	}
+	let mut input = String::new();
+	fallback.read_to_string(&mut input)?;
+	Ok(input)
+}
+
+/// Checks if a `--flag` was passed on the command line.
+#[must_use]
+pub fn has_flag(flag: &str) -> bool {
+	has_flag_in(env::args().skip(1), flag)
+}
+
+/// Checks if a `--flag` is among the arguments.
+fn has_flag_in(args: impl IntoIterator<Item = String>, flag: &str) -> bool {
+	args.into_iter().any(|arg| arg == flag)
+}
+
+#[cfg(test)]
+mod test_read_input {
+	use super::*;
+	use std::{env, process};
+
+	fn args(args: &[&str]) -> Vec<String> {
+		args.iter().map(ToString::to_string).collect()
+	}
+
+	#[test]
+	fn test_file() {
+		let path = env::temp_dir().join(format!("read_input_{}.txt", process::id()));
+		fs::write(&path, "from file\n").unwrap();
+		let path_arg = path.to_str().unwrap();
+		let input = read_input_or(args(&["--quiet", path_arg]), "from stdin".as_bytes());
+		fs::remove_file(&path).unwrap();
+		assert_eq!(input.unwrap(), "from file\n");
+	}
+
+	#[test]
+	fn test_no_args() {
+		let input = read_input_or(args(&[]), "from stdin".as_bytes());
+		assert_eq!(input.unwrap(), "from stdin");
+	}
+
+	#[test]
+	fn test_only_flags() {
+		let input = read_input_or(args(&["--per-row"]), "from stdin".as_bytes());
+		assert_eq!(input.unwrap(), "from stdin");
+	}
+
+	#[test]
+	fn test_missing_file() {
+		let input = read_input_or(args(&["no/such/file.txt"]), "from stdin".as_bytes());
+		assert!(input.is_err());
+	}
+}
+
+#[cfg(test)]
+mod test_has_flag {
+	use super::*;
+
+	#[test]
+	fn test_has_flag_in() {
+		let args = || ["input.txt", "--json"].map(String::from);
+		assert!(has_flag_in(args(), "--json"));
+		assert!(!has_flag_in(args(), "--quiet"));
+		assert!(!has_flag_in(args(), "json"));
+	}
+}
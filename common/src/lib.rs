@@ -0,0 +1,32 @@
+//! Helpers shared between days.
+
+pub mod coords;
+pub mod cycle;
+pub mod day;
+pub mod dijkstra;
+pub mod direction;
+pub mod emit;
+pub mod flood;
+pub mod grid;
+pub mod harness;
+pub mod input;
+pub mod parallel;
+pub mod parse;
+pub mod pretty;
+pub mod sorted;
+pub mod timing;
+
+pub use coords::{checked_add, in_bounds, Coords};
+pub use cycle::{detect_cycle, detect_cycle_within};
+pub use day::Day;
+pub use dijkstra::dijkstra;
+pub use direction::Direction;
+pub use emit::emit;
+pub use flood::flood_fill;
+pub use grid::{parse_char_grid, parse_tile_grid, transpose, Grid, GridError};
+pub use input::{has_flag, read_input};
+pub use parallel::map_chunks;
+pub use parse::ParseError;
+pub use pretty::colourise;
+pub use sorted::sorted;
+pub use timing::timed;
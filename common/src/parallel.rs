@@ -0,0 +1,57 @@
+use std::num::NonZeroUsize;
+use std::thread;
+
+/// Splits `items` into one chunk per available core, and runs `work` on each chunk on its own thread.
+/// Returns the results in chunk order, for the caller to combine.
+/// # Panics
+/// If any thread panics.
+#[must_use]
+pub fn map_chunks<T: Sync, R: Send>(items: &[T], work: impl Fn(&[T]) -> R + Sync) -> Vec<R> {
+	let nb_threads = thread::available_parallelism().map_or(1, NonZeroUsize::get);
+	map_chunks_on(items, nb_threads, work)
+}
+
+/// Like `map_chunks`, with a set number of threads.
+fn map_chunks_on<T: Sync, R: Send>(
+	items: &[T],
+	nb_threads: usize,
+	work: impl Fn(&[T]) -> R + Sync,
+) -> Vec<R> {
+	let chunk_size = items.len().div_ceil(nb_threads).max(1);
+	thread::scope(|scope| {
+		let handles: Vec<_> = items
+			.chunks(chunk_size)
+			.map(|chunk| scope.spawn(|| work(chunk)))
+			.collect();
+		handles
+			.into_iter()
+			.map(|handle| handle.join().expect("Thread panicked"))
+			.collect()
+	})
+}
+
+#[cfg(test)]
+mod test_map_chunks {
+	use super::*;
+
+	#[test]
+	fn test_chunk_order() {
+		let items: Vec<usize> = (1..=10).collect();
+		assert_eq!(
+			map_chunks_on(&items, 3, <[usize]>::to_vec),
+			vec![vec![1, 2, 3, 4], vec![5, 6, 7, 8], vec![9, 10]]
+		);
+	}
+
+	#[test]
+	fn test_sum() {
+		let items: Vec<usize> = (1..=100).collect();
+		let sums = map_chunks(&items, |chunk| chunk.iter().sum::<usize>());
+		assert_eq!(sums.into_iter().sum::<usize>(), 5050);
+	}
+
+	#[test]
+	fn test_empty() {
+		assert_eq!(map_chunks_on(&[] as &[usize], 4, <[usize]>::len), vec![]);
+	}
+}
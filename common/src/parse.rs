@@ -0,0 +1,113 @@
+use std::{error, fmt};
+
+use nom::{
+	error::{Error, ErrorKind},
+	Offset,
+};
+
+/// Reason the input couldn't be parsed, and where.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct ParseError {
+	/// What was being parsed, or what was wrong with it.
+	pub message: String,
+	/// The nom parser that failed, if the error came from nom.
+	pub kind: Option<ErrorKind>,
+	/// Line and column where parsing failed, both counting from 1, if known.
+	pub position: Option<(usize, usize)>,
+}
+
+impl ParseError {
+	/// Builds an error with only a message.
+	#[must_use]
+	pub fn new(message: &str) -> Self {
+		Self {
+			message: String::from(message),
+			kind: None,
+			position: None,
+		}
+	}
+
+	/// Builds an error at the start of `remaining`, a part of the whole `input`.
+	#[must_use]
+	pub fn at(message: &str, input: &str, remaining: &str) -> Self {
+		Self {
+			position: get_position(input, remaining),
+			..Self::new(message)
+		}
+	}
+
+	/// Builds an error from a nom failure while parsing the whole `input`.
+	#[must_use]
+	pub fn from_nom(message: &str, input: &str, error: &nom::Err<Error<&str>>) -> Self {
+		match error {
+			nom::Err::Error(error) | nom::Err::Failure(error) => Self {
+				kind: Some(error.code),
+				..Self::at(message, input, error.input)
+			},
+			nom::Err::Incomplete(_) => Self::new(message),
+		}
+	}
+}
+
+/// Finds the line and column where `remaining`, a part of the whole `input`, starts.
+fn get_position(input: &str, remaining: &str) -> Option<(usize, usize)> {
+	let input_range = input.as_ptr() as usize..=input.as_ptr() as usize + input.len();
+	if !input_range.contains(&(remaining.as_ptr() as usize)) {
+		return None;
+	}
+	let before = &input[..input.offset(remaining)];
+	let line = before.matches('\n').count() + 1;
+	let line_start = before.rfind('\n').map_or(0, |index| index + 1);
+	let column = before[line_start..].chars().count() + 1;
+	Some((line, column))
+}
+
+impl fmt::Display for ParseError {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		write!(f, "{}", self.message)?;
+		if let Some((line, column)) = self.position {
+			write!(f, " at line {line}, column {column}")?;
+		}
+		if let Some(kind) = self.kind {
+			write!(f, " ({})", kind.description())?;
+		}
+		Ok(())
+	}
+}
+
+impl error::Error for ParseError {}
+
+#[cfg(test)]
+mod test_parse_error {
+	use super::*;
+	use nom::character::complete::digit1;
+
+	#[test]
+	fn test_at() {
+		let input = "12\n34x\n";
+		let error = ParseError::at("Unexpected letter", input, &input[5..]);
+		assert_eq!(error.position, Some((2, 3)));
+		assert_eq!(error.to_string(), "Unexpected letter at line 2, column 3");
+	}
+
+	#[test]
+	fn test_outside_input() {
+		let elsewhere = String::from("34");
+		let error = ParseError::at("Elsewhere", "12", &elsewhere);
+		assert_eq!(error.position, None);
+		assert_eq!(error.to_string(), "Elsewhere");
+	}
+
+	#[test]
+	fn test_from_nom() {
+		let input = "12\nab";
+		let nom_error = digit1::<&str, Error<&str>>(&input[3..]).unwrap_err();
+		let error = ParseError::from_nom("Invalid number", input, &nom_error);
+		assert_eq!(error.kind, Some(ErrorKind::Digit));
+		assert_eq!(error.position, Some((2, 1)));
+		assert_eq!(
+			error.to_string(),
+			"Invalid number at line 2, column 1 (Digit)"
+		);
+	}
+}
@@ -0,0 +1,56 @@
+use std::{
+	env,
+	ffi::OsString,
+	fmt,
+	io::{self, IsTerminal},
+};
+
+/// A colour from the 256-colour ANSI palette.
+pub type AnsiColour = u8;
+
+/// Applies a pretty terminal colour, unless colours are turned off:
+/// when the `NO_COLOR` environment variable is set, or stdout isn't a terminal.
+#[must_use]
+pub fn colourise(
+	symbol: impl fmt::Display,
+	background: AnsiColour,
+	foreground: AnsiColour,
+) -> String {
+	if colours_enabled(env::var_os("NO_COLOR"), io::stdout().is_terminal()) {
+		paint(symbol, background, foreground)
+	} else {
+		symbol.to_string()
+	}
+}
+
+/// Checks if colours are on, given the value of `NO_COLOR` and whether stdout is a terminal.
+/// An empty `NO_COLOR` counts as unset.
+#[must_use]
+fn colours_enabled(no_color: Option<OsString>, is_terminal: bool) -> bool {
+	no_color.is_none_or(|value| value.is_empty()) && is_terminal
+}
+
+/// Wraps a symbol in the ANSI codes for its colours.
+#[must_use]
+fn paint(symbol: impl fmt::Display, background: AnsiColour, foreground: AnsiColour) -> String {
+	format!("\x1b[38;5;{foreground};48;5;{background}m{symbol}\x1b[0m")
+}
+
+#[cfg(test)]
+mod test_colourise {
+	use super::*;
+
+	#[test]
+	fn test_paint() {
+		let colourised = paint('╬', 100, 200);
+		assert_eq!(colourised, "\x1b[38;5;200;48;5;100m╬\x1b[0m");
+	}
+
+	#[test]
+	fn test_colours_enabled() {
+		assert!(colours_enabled(None, true));
+		assert!(colours_enabled(Some(OsString::new()), true));
+		assert!(!colours_enabled(Some(OsString::from("1")), true));
+		assert!(!colours_enabled(None, false));
+	}
+}
@@ -0,0 +1,20 @@
+/// Collects items into a sorted list, so sets come out in the same order every run.
+#[must_use]
+pub fn sorted<T: Ord>(items: impl IntoIterator<Item = T>) -> Vec<T> {
+	let mut items: Vec<T> = items.into_iter().collect();
+	items.sort();
+	items
+}
+
+#[cfg(test)]
+mod test_sorted {
+	use std::collections::HashSet;
+
+	use super::*;
+
+	#[test]
+	fn test_sorted() {
+		assert_eq!(sorted(HashSet::from([3, 1, 2])), vec![1, 2, 3]);
+		assert_eq!(sorted(Vec::<u8>::new()), vec![]);
+	}
+}
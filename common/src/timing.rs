@@ -0,0 +1,20 @@
+use std::time::Instant;
+
+/// Runs a computation, prints how long it took in microseconds to stderr, and returns its result.
+pub fn timed<T>(label: &str, compute: impl FnOnce() -> T) -> T {
+	let now = Instant::now();
+	let result = compute();
+	eprintln!("{label}: {} µs", now.elapsed().as_micros());
+	result
+}
+
+#[cfg(test)]
+mod test_timed {
+	use super::*;
+
+	#[test]
+	fn test_returns_value() {
+		assert_eq!(timed("Answer", || 6 * 7), 42);
+		assert_eq!(timed("Text", || String::from("unchanged")), "unchanged");
+	}
+}
@@ -0,0 +1,159 @@
+use common::Day;
+
+type DigitMatch = (&'static str, &'static str, u32);
+
+const MATCHES: &[DigitMatch] = &[
+	("1", "one", 1),
+	("2", "two", 2),
+	("3", "three", 3),
+	("4", "four", 4),
+	("5", "five", 5),
+	("6", "six", 6),
+	("7", "seven", 7),
+	("8", "eight", 8),
+	("9", "nine", 9),
+];
+
+/// Finds the first (or last, if `backwards`) digit in a line, if it has any.
+#[must_use]
+fn find_digit(s: &str, include_words: bool, backwards: bool) -> Option<u32> {
+	let pred = if backwards {
+		str::ends_with
+	} else {
+		str::starts_with
+	};
+
+	if s.is_empty() {
+		return None;
+	}
+
+	for (digit_match, word_match, value) in MATCHES {
+		if pred(s, digit_match) || include_words && pred(s, word_match) {
+			return Some(*value);
+		}
+	}
+
+	let rest = if backwards {
+		&s[..s.len() - 1]
+	} else {
+		&s[1..]
+	};
+	find_digit(rest, include_words, backwards)
+}
+
+/// Combines the first and last digits of a line into a number, or 0 if the line has no digits.
+#[must_use]
+fn get_digits(s: &str, include_words: bool) -> u32 {
+	let first_digit = find_digit(s, include_words, false);
+	let last_digit = find_digit(s, include_words, true);
+	first_digit
+		.zip(last_digit)
+		.map_or(0, |(first_digit, last_digit)| 10 * first_digit + last_digit)
+}
+
+#[must_use]
+pub fn get_total(s: &str, include_words: bool) -> u32 {
+	s.lines().map(|line| get_digits(line, include_words)).sum()
+}
+
+/// Totals both parts in one pass over the lines: numeric digits only, and with spelled-out digits.
+#[must_use]
+pub fn solve_both(s: &str) -> (u32, u32) {
+	s.lines().fold((0, 0), |(numeric, with_letters), line| {
+		(
+			numeric + get_digits(line, false),
+			with_letters + get_digits(line, true),
+		)
+	})
+}
+
+/// Both parts, for running this day alongside the others.
+pub struct Day01;
+
+impl Day for Day01 {
+	fn part1(input: &str) -> String {
+		get_total(input, false).to_string()
+	}
+
+	fn part2(input: &str) -> String {
+		get_total(input, true).to_string()
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+
+	#[test]
+	fn test_get_digits_only_numeric() {
+		assert_eq!(get_digits("1abc2", false), 12);
+		assert_eq!(get_digits("pqr3stu8vwx", false), 38);
+		assert_eq!(get_digits("a1b2c3d4e5f", false), 15);
+		assert_eq!(get_digits("treb7uchet", false), 77);
+	}
+
+	#[test]
+	fn test_get_digits_ignoring_letters() {
+		assert_eq!(get_digits("two1nine", false), 11);
+		assert_eq!(get_digits("abcone2threexyz", false), 22);
+		assert_eq!(get_digits("xtwone3four", false), 33);
+		assert_eq!(get_digits("4nineeightseven2", false), 42);
+		assert_eq!(get_digits("zoneight234", false), 24);
+		assert_eq!(get_digits("7pqrstsixteen", false), 77);
+	}
+
+	#[test]
+	fn test_get_digits_with_letters() {
+		assert_eq!(get_digits("two1nine", true), 29);
+		assert_eq!(get_digits("eightwothree", true), 83);
+		assert_eq!(get_digits("abcone2threexyz", true), 13);
+		assert_eq!(get_digits("xtwone3four", true), 24);
+		assert_eq!(get_digits("4nineeightseven2", true), 42);
+		assert_eq!(get_digits("zoneight234", true), 14);
+		assert_eq!(get_digits("zoneight", true), 18);
+		assert_eq!(get_digits("7pqrstsixteen", true), 76);
+		assert_eq!(get_digits("thebeautyofourweapons", true), 44);
+	}
+
+	#[test]
+	fn test_single_line() {
+		assert_eq!(get_total("1", false), 11);
+	}
+
+	#[test]
+	fn test_no_digits() {
+		assert_eq!(get_digits("eightwothree", false), 0);
+		assert_eq!(get_digits("", true), 0);
+	}
+
+	#[test]
+	fn test_sample_a() {
+		const SAMPLE_INPUT: &str = include_str!("../input_sample_a.txt");
+		assert_eq!(get_total(SAMPLE_INPUT, false), 142);
+	}
+
+	#[test]
+	fn test_sample_b() {
+		const SAMPLE_INPUT: &str = include_str!("../input_sample_b.txt");
+		assert_eq!(get_total(SAMPLE_INPUT, true), 281);
+	}
+
+	#[test]
+	fn test_solve_both() {
+		let sample_a = include_str!("../input_sample_a.txt");
+		assert_eq!(solve_both(sample_a), (142, 142));
+
+		// "eightwothree" has no numeric digit, so it counts 0 without letters.
+		let sample_b = include_str!("../input_sample_b.txt");
+		assert_eq!(solve_both(sample_b).1, 281);
+		assert_eq!(solve_both(sample_b), (209, 281));
+	}
+
+	#[test]
+	fn test_day_trait() {
+		let sample_a = include_str!("../input_sample_a.txt");
+		let sample_b = include_str!("../input_sample_b.txt");
+		assert_eq!(Day01::part1(sample_a), "142");
+		assert_eq!(Day01::part2(sample_b), "281");
+	}
+}
@@ -0,0 +1,14 @@
+use common::harness::run_sample;
+
+#[test]
+fn test_sample() {
+	assert_eq!(
+		run_sample(
+			env!("CARGO_BIN_EXE_day01"),
+			env!("CARGO_MANIFEST_DIR"),
+			"input_sample_a.txt",
+			&[]
+		),
+		"Numeric only: 142\nWith letters: 142\n"
+	);
+}
@@ -1,6 +1,8 @@
 use game::{Game, Hand};
 use parse_input::parse_all;
-use std::io::{self, Read};
+use std::env;
+
+use common::{emit, has_flag, read_input, timed};
 
 mod game;
 mod parse_input;
@@ -38,12 +40,16 @@ mod test {
 }
 
 fn main() {
-	let mut input = String::new();
-	io::stdin()
-		.read_to_string(&mut input)
-		.expect("Failed to read input");
+	let input = read_input(env::args().skip(1)).expect("Failed to read input");
 	let games = parse_all(&input);
+	let json = has_flag("--json");
 
-	println!("Possible games: {}", sum_possible_games(&games));
-	println!("Power: {}", sum_power(&games));
+	emit(
+		(
+			"Possible games",
+			timed("Part 1", || sum_possible_games(&games)),
+		),
+		("Power", timed("Part 2", || sum_power(&games))),
+		json,
+	);
 }
@@ -4,6 +4,8 @@ use nom::error::{Error, ErrorKind};
 use nom::multi::{many1, separated_list1};
 use nom::IResult;
 
+use common::ParseError;
+
 use crate::game::{Game, Hand};
 
 enum Colour {
@@ -89,19 +91,28 @@ fn game_list(input: &str) -> IResult<&str, Vec<Game>> {
 	many1(game)(input)
 }
 
+/// Parses the full list of games.
+/// # Errors
+/// On a malformed game, or anything else after the games.
+pub fn try_parse_all(input: &str) -> Result<Vec<Game>, ParseError> {
+	let (remaining, games) =
+		game_list(input).map_err(|error| ParseError::from_nom("Invalid game", input, &error))?;
+	if !remaining.trim().is_empty() {
+		// Find out why the next game didn't parse
+		return Err(game(remaining).map_or_else(
+			|error| ParseError::from_nom("Invalid game", input, &error),
+			|_| ParseError::at("Unexpected trailing after input", input, remaining),
+		));
+	}
+	Ok(games)
+}
+
 /// Parses the full list of games.
 /// # Panics
 /// On any parse error.
 #[must_use]
 pub fn parse_all(input: &str) -> Vec<Game> {
-	let (remaining, games) =
-		game_list(input).unwrap_or_else(|error| panic!("Parse error: {error}"));
-	assert_eq!(
-		remaining.trim(),
-		"",
-		"Unexpected trailing after input: {remaining}"
-	);
-	games
+	try_parse_all(input).unwrap_or_else(|error| panic!("Parse error: {error}"))
 }
 
 #[cfg(test)]
@@ -165,4 +176,18 @@ mod test {
 		];
 		assert_eq!(parsed, expected);
 	}
+
+	#[test]
+	fn test_invalid_colour() {
+		let error = try_parse_all("Game 1: 3 red\nGame 2: 3 purple\n").unwrap_err();
+		assert_eq!(error.message, "Invalid game");
+		assert_eq!(error.kind, Some(ErrorKind::Alpha));
+		assert_eq!(error.position, Some((2, 11)));
+	}
+
+	#[test]
+	fn test_trailing() {
+		let error = try_parse_all("Game 1: 3 red\nnonsense\n").unwrap_err();
+		assert_eq!(error.to_string(), "Invalid game at line 2, column 1 (Tag)");
+	}
 }
@@ -0,0 +1,14 @@
+use common::harness::run_sample;
+
+#[test]
+fn test_sample() {
+	assert_eq!(
+		run_sample(
+			env!("CARGO_BIN_EXE_day02"),
+			env!("CARGO_MANIFEST_DIR"),
+			"input_sample.txt",
+			&[]
+		),
+		"Possible games: 8\nPower: 2286\n"
+	);
+}
@@ -1,3 +1,5 @@
+use common::{parse_char_grid, Grid};
+
 /// A horizontal span on the 2D board, occupied by a number.
 #[derive(Debug, Clone, Copy, Eq, Hash, Ord, PartialEq, PartialOrd)]
 pub struct Span {
@@ -15,19 +17,17 @@ fn is_adjacent((row, col): (usize, usize), span: &Span) -> bool {
 /// A 2D char array.
 #[derive(Debug, Clone, Eq, Hash, Ord, PartialEq, PartialOrd)]
 pub struct Board {
-	pub nb_rows: usize,
-	pub nb_cols: usize,
-	pub tiles: Vec<Vec<char>>,
+	pub tiles: Grid<char>,
 }
 
 /// Reads a board from a string.
+/// # Panics
+/// If the board is empty or not rectangular.
 #[must_use]
 pub fn parse(s: &str) -> Board {
-	let tiles: Vec<Vec<char>> = s.lines().map(|line| line.chars().collect()).collect();
-	Board {
-		nb_rows: tiles.len(),
-		nb_cols: tiles.first().expect("Empty board").len(),
-		tiles,
+	match parse_char_grid(s) {
+		Ok(tiles) => Board { tiles },
+		Err(err) => panic!("Invalid board: {err}"),
 	}
 }
 
@@ -42,9 +42,9 @@ impl Board {
 	#[must_use]
 	fn find_numbers(&self) -> Vec<Span> {
 		let mut spans: Vec<Span> = vec![];
-		for row in 0..self.nb_rows {
+		for row in 0..self.tiles.rows() {
 			let mut start_digit: Option<usize> = None;
-			for col in 0..self.nb_cols {
+			for col in 0..self.tiles.cols() {
 				if self.tiles[row][col].is_ascii_digit() {
 					// Start a span if this is the first digit.
 					if start_digit.is_none() {
@@ -68,7 +68,7 @@ impl Board {
 				let new_span = Span {
 					row,
 					start_col,
-					end_col: self.nb_cols,
+					end_col: self.tiles.cols(),
 				};
 				spans.push(new_span);
 			}
@@ -99,10 +99,10 @@ impl Board {
 		} else {
 			0
 		};
-		let max_col = if span.end_col < self.nb_cols {
+		let max_col = if span.end_col < self.tiles.cols() {
 			span.end_col + 1
 		} else {
-			self.nb_cols
+			self.tiles.cols()
 		};
 
 		// Above
@@ -115,7 +115,7 @@ impl Board {
 		}
 
 		// Below
-		if span.row < self.nb_cols - 1 {
+		if span.row < self.tiles.cols() - 1 {
 			for col in min_col..max_col {
 				if is_symbol(self.tiles[span.row + 1][col]) {
 					return true;
@@ -129,7 +129,7 @@ impl Board {
 		}
 
 		// Right
-		if span.end_col < self.nb_cols && is_symbol(self.tiles[span.row][span.end_col]) {
+		if span.end_col < self.tiles.cols() && is_symbol(self.tiles[span.row][span.end_col]) {
 			return true;
 		}
 
@@ -152,8 +152,8 @@ impl Board {
 		let spans = self.find_numbers();
 		let mut gears = vec![];
 
-		for row in 0..self.nb_rows {
-			for col in 0..self.nb_cols {
+		for row in 0..self.tiles.rows() {
+			for col in 0..self.tiles.cols() {
 				if self.tiles[row][col] == '*' {
 					let adjacent: Vec<&Span> = spans
 						.iter()
@@ -198,13 +198,17 @@ mod test {
 	fn test_to_board() {
 		let input = ".12\n*..\n";
 		let expected = Board {
-			nb_rows: 2,
-			nb_cols: 3,
-			tiles: vec![vec!['.', '1', '2'], vec!['*', '.', '.']],
+			tiles: Grid::from(vec![vec!['.', '1', '2'], vec!['*', '.', '.']]),
 		};
 		assert_eq!(parse(&input), expected);
 	}
 
+	#[test]
+	#[should_panic(expected = "Invalid board: Ragged grid")]
+	fn test_ragged_board() {
+		let _ = parse(".12\n*.\n");
+	}
+
 	#[test]
 	fn test_find_numbers() {
 		let board = parse("1.2\n345\n.67\n");
@@ -1,5 +1,7 @@
 use board::{parse, Board};
-use std::io::{self, Read};
+use std::env;
+
+use common::{emit, has_flag, read_input, timed};
 
 mod board;
 
@@ -27,13 +29,17 @@ mod test {
 }
 
 fn main() {
-	let mut input = String::new();
-	io::stdin()
-		.read_to_string(&mut input)
-		.expect("Failed to read input");
+	let input = read_input(env::args().skip(1)).expect("Failed to read input");
 
 	let board = parse(&input);
-
-	println!("Part numbers sum: {}", sum_part_numbers(&board));
-	println!("Gears sum: {}", sum_gears(&board));
+	let json = has_flag("--json");
+
+	emit(
+		(
+			"Part numbers sum",
+			timed("Part 1", || sum_part_numbers(&board)),
+		),
+		("Gears sum", timed("Part 2", || sum_gears(&board))),
+		json,
+	);
 }
@@ -0,0 +1,14 @@
+use common::harness::run_sample;
+
+#[test]
+fn test_sample() {
+	assert_eq!(
+		run_sample(
+			env!("CARGO_BIN_EXE_day03"),
+			env!("CARGO_MANIFEST_DIR"),
+			"input_sample.txt",
+			&[]
+		),
+		"Part numbers sum: 4361\nGears sum: 467835\n"
+	);
+}
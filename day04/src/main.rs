@@ -1,9 +1,8 @@
 use card_list::CardList;
 use scratchcard::Scratchcard;
-use std::{
-	io::{self, Read},
-	time::Instant,
-};
+use std::env;
+
+use common::{emit, has_flag, read_input, timed};
 
 use crate::parse_input::parse_cards;
 
@@ -39,20 +38,13 @@ mod test {
 }
 
 fn main() {
-	let mut input = String::new();
-	io::stdin()
-		.read_to_string(&mut input)
-		.expect("Failed to read input");
-
-	let now = Instant::now();
-
-	let cards = parse_cards(&input);
-	let part1 = get_total_score(&cards);
-	let part2 = get_total_cards(&cards);
-
-	let elapsed = now.elapsed();
-
-	println!("Score: {part1}");
-	println!("Cards: {part2}");
-	println!("Time: {} µs", elapsed.as_micros());
+	let input = read_input(env::args().skip(1)).expect("Failed to read input");
+
+	let cards = timed("Parse", || parse_cards(&input));
+	let json = has_flag("--json");
+	emit(
+		("Score", timed("Part 1", || get_total_score(&cards))),
+		("Cards", timed("Part 2", || get_total_cards(&cards))),
+		json,
+	);
 }
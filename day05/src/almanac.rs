@@ -314,7 +314,7 @@ foo-to-bar map:
 bar-to-baz map:
 0 1000 1
 ";
-		let (mut almanac, converter) = parse_full(input, false);
+		let (mut almanac, _, converter) = parse_full(input);
 
 		converter.convert_all(&mut almanac);
 
@@ -1,16 +1,21 @@
-use std::io::{self, Read};
+use std::env;
 
 use almanac::Almanac;
+use common::{emit, has_flag, read_input, timed};
 use parse_input::parse_full;
 
 mod almanac;
 mod parse_input;
 
+/// Converts the seeds, read as points then as ranges if they pair up, with the same maps.
 #[must_use]
-fn get_all(s: &str, as_ranges: bool) -> Almanac {
-	let (mut almanac, converter) = parse_full(s, as_ranges);
-	converter.convert_all(&mut almanac);
-	almanac
+fn get_all(s: &str) -> (Almanac, Option<Almanac>) {
+	let (mut points, mut ranges, converter) = parse_full(s);
+	converter.convert_all(&mut points);
+	if let Some(ranges) = &mut ranges {
+		converter.convert_all(ranges);
+	}
+	(points, ranges)
 }
 
 #[must_use]
@@ -22,6 +27,14 @@ fn min_location(almanac: &Almanac) -> u64 {
 		.expect("No almanacs found")
 }
 
+/// Answers both parts: reads the seeds as points, then as ranges, and converts both with the same maps.
+/// There's no part 2 answer if the seeds don't pair up into ranges.
+#[must_use]
+fn solve_both(input: &str) -> (u64, Option<u64>) {
+	let (points, ranges) = get_all(input);
+	(min_location(&points), ranges.as_ref().map(min_location))
+}
+
 #[cfg(test)]
 mod test {
 	use std::collections::HashSet;
@@ -31,7 +44,7 @@ mod test {
 
 	#[test]
 	fn test_sample_part1() {
-		let almanac = get_all(SAMPLE_INPUT, false);
+		let (almanac, _) = get_all(SAMPLE_INPUT);
 		assert_eq!(
 			almanac["seed"],
 			HashSet::from([(79, 80), (14, 15), (55, 56), (13, 14)])
@@ -50,23 +63,32 @@ mod test {
 
 	#[test]
 	fn test_sample_part2() {
-		let almanac: std::collections::HashMap<String, HashSet<(u64, u64)>> =
-			get_all(SAMPLE_INPUT, true);
+		let almanac = get_all(SAMPLE_INPUT).1.expect("Seeds should pair up");
 		assert_eq!(almanac["seed"], HashSet::from([(79, 93), (55, 68)]));
 
 		assert_eq!(min_location(&almanac), 46);
 	}
+
+	#[test]
+	fn test_sample_solve_both() {
+		assert_eq!(solve_both(SAMPLE_INPUT), (35, Some(46)));
+	}
+
+	#[test]
+	fn test_unpaired_seeds_part1_only() {
+		let input = SAMPLE_INPUT.replacen("seeds: 79 14 55 13", "seeds: 79 14 55", 1);
+		assert_eq!(solve_both(&input), (43, None));
+	}
 }
 
 fn main() {
-	let mut input = String::new();
-	io::stdin()
-		.read_to_string(&mut input)
-		.expect("Failed to read input");
-
-	let almanac_points = get_all(&input, false);
-	println!("Part 1: {}", min_location(&almanac_points));
+	let input = read_input(env::args().skip(1)).expect("Failed to read input");
 
-	let almanac_ranges = get_all(&input, true);
-	println!("Part 2: {}", min_location(&almanac_ranges));
+	let json = has_flag("--json");
+	let (part1, part2) = timed("Both parts", || solve_both(&input));
+	let part2 = part2.map_or_else(
+		|| String::from("none, seeds don't pair up"),
+		|x| x.to_string(),
+	);
+	emit(("Part 1", part1), ("Part 2", part2), json);
 }
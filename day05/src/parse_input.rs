@@ -5,6 +5,8 @@ use nom::{
 	IResult,
 };
 
+use common::ParseError;
+
 use crate::almanac::{Almanac, ConversionMap, ConversionRange, Converter};
 
 /// Consumes the name of a value type.
@@ -96,25 +98,41 @@ fn conversion_map(input: &str) -> IResult<&str, ConversionMap> {
 	))
 }
 
-/// Consumes the whole input.
-fn full(input: &str, as_ranges: bool) -> IResult<&str, (Almanac, Converter)> {
-	let parser = if as_ranges {
-		almanac_ranges
-	} else {
-		almanac_points
-	};
-	let (input, almanac) = parser(input)?;
-	let (input, maps) = many1(conversion_map)(input)?;
-	Ok((input, (almanac, Converter { maps })))
+/// Consumes the whole input, reading the first list both as single values and as ranges.
+/// The ranges are `None` if the values don't pair up.
+fn full(input: &str) -> IResult<&str, (Almanac, Option<Almanac>, Converter)> {
+	let (after_points, points) = almanac_points(input)?;
+	let ranges = almanac_ranges(input)
+		.ok()
+		.filter(|&(after_ranges, _)| after_ranges == after_points)
+		.map(|(_, ranges)| ranges);
+	let (input, maps) = many1(conversion_map)(after_points)?;
+	Ok((input, (points, ranges, Converter { maps })))
+}
+
+/// Parses the whole input, into the first list as points, then as ranges, then the maps.
+/// The ranges are `None` if the first list has an odd number of values.
+/// # Errors
+/// On a malformed almanac or conversion map, or anything else after the maps.
+pub fn try_parse_full(input: &str) -> Result<(Almanac, Option<Almanac>, Converter), ParseError> {
+	let (remaining, parsed) =
+		full(input).map_err(|error| ParseError::from_nom("Invalid almanac", input, &error))?;
+	if !remaining.trim().is_empty() {
+		// Find out why the next map didn't parse
+		return Err(conversion_map(remaining).map_or_else(
+			|error| ParseError::from_nom("Invalid conversion map", input, &error),
+			|_| ParseError::at("Unexpected trailing after input", input, remaining),
+		));
+	}
+	Ok(parsed)
 }
 
-/// Parses the whole input.
+/// Parses the whole input, into the first list as points, then as ranges, then the maps.
 /// # Panics
 /// On any parse error.
 #[must_use]
-pub fn parse_full(input: &str, as_ranges: bool) -> (Almanac, Converter) {
-	let (_, parsed) = full(input, as_ranges).expect("Parse error");
-	parsed
+pub fn parse_full(input: &str) -> (Almanac, Option<Almanac>, Converter) {
+	try_parse_full(input).unwrap_or_else(|error| panic!("Parse error: {error}"))
 }
 
 #[cfg(test)]
@@ -212,12 +230,13 @@ mod test {
 	}
 
 	#[test]
-	fn test_full_as_points() {
+	fn test_full() {
 		let foos = "foos: 1 2\n";
 		let foo_to_bar = "foo-to-bar map:\n3 10 1\n";
 		let bar_to_baz: &str = "bar-to-baz map:\n10 100 10\n4 20 2\n";
 
-		let (_, almanac) = almanac_points(foos).unwrap();
+		let (_, points) = almanac_points(foos).unwrap();
+		let (_, ranges) = almanac_ranges(foos).unwrap();
 		let (_, map1) = conversion_map(foo_to_bar).unwrap();
 		let (_, map2) = conversion_map(bar_to_baz).unwrap();
 		let converter = Converter {
@@ -225,23 +244,37 @@ mod test {
 		};
 
 		let input = [foos, foo_to_bar, bar_to_baz].join("\n");
-		let (_, parsed) = full(&input, false).expect("Parse error");
+		let (_, parsed) = full(&input).expect("Parse error");
 
-		assert_eq!(parsed, (almanac, converter));
+		assert_eq!(parsed, (points, Some(ranges), converter));
 	}
 
 	#[test]
-	fn test_full_as_ranges() {
-		let foos = "foos: 1 2\n";
-		let foo_to_bar = "foo-to-bar map:\n3 10 1\n";
-
-		let (_, almanac) = almanac_ranges(foos).unwrap();
-		let (_, map) = conversion_map(foo_to_bar).unwrap();
-		let converter = Converter { maps: vec![map] };
+	fn test_unpaired_value() {
+		let input = "seeds: 79 14 55\n\nseed-to-soil map:\n50 98 2\n";
+		let (points, ranges, _) = try_parse_full(input).expect("Parse error");
+		assert_eq!(
+			points["seed"],
+			HashSet::from([(79, 80), (14, 15), (55, 56)])
+		);
+		assert_eq!(ranges, None);
+	}
 
-		let input = [foos, foo_to_bar].join("\n");
-		let (_, parsed) = full(&input, true).expect("Parse error");
+	#[test]
+	fn test_invalid_range() {
+		let input = "seeds: 79 14\n\nseed-to-soil map:\n50 98 x\n";
+		let error = try_parse_full(input).unwrap_err();
+		assert_eq!(
+			error.to_string(),
+			"Invalid almanac at line 4, column 7 (Digit)"
+		);
+	}
 
-		assert_eq!(parsed, (almanac, converter));
+	#[test]
+	fn test_invalid_later_map() {
+		let input = "seeds: 79 14\n\nseed-to-soil map:\n50 98 2\n\nsoil-to-fertilizer map:\n0 15\n";
+		let error = try_parse_full(input).unwrap_err();
+		assert_eq!(error.message, "Invalid conversion map");
+		assert_eq!(error.position, Some((7, 5)));
 	}
 }
@@ -0,0 +1,27 @@
+use common::harness::run_sample;
+
+#[test]
+fn test_sample() {
+	assert_eq!(
+		run_sample(
+			env!("CARGO_BIN_EXE_day05"),
+			env!("CARGO_MANIFEST_DIR"),
+			"input_sample.txt",
+			&[]
+		),
+		"Part 1: 35\nPart 2: 46\n"
+	);
+}
+
+#[test]
+fn test_sample_json() {
+	assert_eq!(
+		run_sample(
+			env!("CARGO_BIN_EXE_day05"),
+			env!("CARGO_MANIFEST_DIR"),
+			"input_sample.txt",
+			&["--json"]
+		),
+		"{\"part1\": 35, \"part2\": 46}\n"
+	);
+}
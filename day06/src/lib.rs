@@ -0,0 +1,46 @@
+use common::Day;
+
+use crate::parse_input::{parse_multi_races, parse_single_race};
+use crate::race::Race;
+
+mod parse_input;
+mod race;
+
+/// Gets the total number of ways to win all races.
+#[must_use]
+pub fn get_nb_permutations(s: &str) -> i64 {
+	let races = parse_multi_races(s);
+	races.iter().map(Race::nb_winning_holds).product()
+}
+
+/// Gets the total number of ways to win a single race.
+#[must_use]
+pub fn get_nb_single(s: &str) -> i64 {
+	let race = parse_single_race(s);
+	race.nb_winning_holds()
+}
+
+/// Both parts, for running this day alongside the others.
+pub struct Day06;
+
+impl Day for Day06 {
+	fn part1(input: &str) -> String {
+		get_nb_permutations(input).to_string()
+	}
+
+	fn part2(input: &str) -> String {
+		get_nb_single(input).to_string()
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+	const SAMPLE_INPUT: &str = include_str!("../input_sample.txt");
+
+	#[test]
+	fn test_sample() {
+		assert_eq!(get_nb_permutations(SAMPLE_INPUT), 288);
+		assert_eq!(get_nb_single(SAMPLE_INPUT), 71503);
+	}
+}
@@ -0,0 +1,14 @@
+use common::harness::run_sample;
+
+#[test]
+fn test_sample() {
+	assert_eq!(
+		run_sample(
+			env!("CARGO_BIN_EXE_day06"),
+			env!("CARGO_MANIFEST_DIR"),
+			"input_sample.txt",
+			&[]
+		),
+		"Total winning holds, multis: 288\nTotal winning holds, single: 71503\n"
+	);
+}
@@ -1,7 +1,9 @@
 use parse_input::parse_full;
 
 use crate::hand::Bid;
-use std::io::{self, Read};
+use std::env;
+
+use common::{emit, has_flag, read_input, timed};
 
 mod card;
 mod hand;
@@ -62,11 +64,12 @@ mod test {
 }
 
 fn main() {
-	let mut input = String::new();
-	io::stdin()
-		.read_to_string(&mut input)
-		.expect("Failed to read input");
+	let input = read_input(env::args().skip(1)).expect("Failed to read input");
 
-	println!("Part 1 — no jokers: {}", part1(&input));
-	println!("Part 2 — jokers: {}", part2(&input));
+	let json = has_flag("--json");
+	emit(
+		("Part 1 — no jokers", timed("Part 1", || part1(&input))),
+		("Part 2 — jokers", timed("Part 2", || part2(&input))),
+		json,
+	);
 }
@@ -1,9 +1,11 @@
 use nom::{
 	character::complete::{multispace1, one_of, space1, u32},
-	multi::many1,
+	multi::{count, many1},
 	IResult,
 };
 
+use common::ParseError;
+
 use crate::{
 	card::Card::{
 		self, Ace, Deuce, Eight, Five, Four, Jack, Joker, King, Nine, Queen, Seven, Six, Ten, Three,
@@ -55,9 +57,9 @@ impl Parser {
 		Ok((input, card))
 	}
 
-	/// Consumes a hand of cards.
+	/// Consumes a hand of 5 cards.
 	fn hand<'a>(&'a self, input: &'a str) -> IResult<&str, Hand> {
-		let (input, cards) = many1(|s| self.card(s))(input)?;
+		let (input, cards) = count(|s| self.card(s), 5)(input)?;
 		Ok((input, Hand::from(cards)))
 	}
 
@@ -74,17 +76,34 @@ impl Parser {
 		Ok((input, bid))
 	}
 	/// Parses the whole input.
-	/// # Panics
-	/// On any parse error.
-	#[must_use]
-	pub fn full(&self, input: &str) -> Vec<Bid> {
-		let (_, bids) = many1(|s| self.bid(s))(input).expect("Parse error");
-		bids
+	/// # Errors
+	/// On a malformed bid, or anything else after the bids.
+	pub fn try_full(&self, input: &str) -> Result<Vec<Bid>, ParseError> {
+		let (remaining, bids) = many1(|s| self.bid(s))(input)
+			.map_err(|error| ParseError::from_nom("Invalid bid", input, &error))?;
+		if !remaining.trim().is_empty() {
+			// Find out why the next bid didn't parse
+			return Err(self.bid(remaining).map_or_else(
+				|error| ParseError::from_nom("Invalid bid", input, &error),
+				|_| ParseError::at("Unexpected trailing after input", input, remaining),
+			));
+		}
+		Ok(bids)
 	}
 }
 
+/// Parses the whole input.
+/// # Errors
+/// On a malformed bid, or anything else after the bids.
+pub fn try_parse_full(input: &str, jacks_are_jokers: bool) -> Result<Vec<Bid>, ParseError> {
+	Parser::new(jacks_are_jokers).try_full(input)
+}
+
+/// Parses the whole input.
+/// # Panics
+/// On any parse error.
 pub fn parse_full(input: &str, jacks_are_jokers: bool) -> Vec<Bid> {
-	Parser::new(jacks_are_jokers).full(input)
+	try_parse_full(input, jacks_are_jokers).unwrap_or_else(|error| panic!("Parse error: {error}"))
 }
 
 #[cfg(test)]
@@ -144,8 +163,20 @@ mod test {
 		let (_, bid1) = Parser::default().bid(input_bid1).unwrap();
 		let (_, bid2) = Parser::default().bid(input_bid2).unwrap();
 
-		let bids = Parser::default().full(&input_list);
+		let bids = Parser::default().try_full(&input_list).unwrap();
 
 		assert_eq!(bids, vec![bid1, bid2]);
 	}
+
+	#[test]
+	fn test_invalid_card() {
+		let error = try_parse_full("AA2KK 23\n42X2Q 42\n", false).unwrap_err();
+		assert_eq!(error.to_string(), "Invalid bid at line 2, column 3 (OneOf)");
+	}
+
+	#[test]
+	fn test_too_many_cards() {
+		let error = try_parse_full("AA2KKK 23\n", false).unwrap_err();
+		assert_eq!(error.position, Some((1, 6)));
+	}
 }
@@ -1,5 +1,6 @@
-use std::io::{self, Read};
+use std::env;
 
+use common::{emit, has_flag, read_input, timed};
 use graph::{ends_with, exact};
 
 use crate::parse_input::parse_full;
@@ -46,11 +47,18 @@ mod test {
 }
 
 fn main() {
-	let mut input = String::new();
-	io::stdin()
-		.read_to_string(&mut input)
-		.expect("Failed to read input");
+	let input = read_input(env::args().skip(1)).expect("Failed to read input");
 
-	println!("Single path (Part 1): {}", traverse_single(&input));
-	println!("Multi-path (Part 2): {}", traverse_multiple(&input));
+	let json = has_flag("--json");
+	emit(
+		(
+			"Single path (Part 1)",
+			timed("Part 1", || traverse_single(&input)),
+		),
+		(
+			"Multi-path (Part 2)",
+			timed("Part 2", || traverse_multiple(&input)),
+		),
+		json,
+	);
 }
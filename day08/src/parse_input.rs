@@ -7,6 +7,8 @@ use nom::{
 	IResult,
 };
 
+use common::ParseError;
+
 use crate::graph::{Game, Instruction, Node};
 
 /// Consumes one instruction.
@@ -47,17 +49,33 @@ fn nodes(input: &str) -> IResult<&str, HashMap<&str, Node>> {
 	Ok((input, nodes))
 }
 
+/// Parses the whole input.
+/// # Errors
+/// On malformed instructions or nodes, or anything else after the nodes.
+pub fn try_parse_full(input: &str) -> Result<Game<'_>, ParseError> {
+	let (remaining, instructions) = instructions(input)
+		.map_err(|error| ParseError::from_nom("Invalid instructions", input, &error))?;
+	let (remaining, nodes) = nodes(remaining)
+		.map_err(|error| ParseError::from_nom("Invalid graph nodes", input, &error))?;
+	if !remaining.trim().is_empty() {
+		// Find out why the next node didn't parse
+		return Err(node(remaining).map_or_else(
+			|error| ParseError::from_nom("Invalid graph nodes", input, &error),
+			|_| ParseError::at("Unexpected trailing after input", input, remaining),
+		));
+	}
+	Ok(Game {
+		instructions,
+		nodes,
+	})
+}
+
 /// Parses the whole input.
 /// # Panics
 /// On any parse error.
 #[must_use]
 pub fn parse_full(input: &str) -> Game {
-	let (input, instructions) = instructions(input).expect("Invalid instructions");
-	let (_, nodes) = nodes(input).expect("Invalid graph nodes");
-	Game {
-		instructions,
-		nodes,
-	}
+	try_parse_full(input).unwrap_or_else(|error| panic!("Parse error: {error}"))
 }
 
 #[cfg(test)]
@@ -97,4 +115,20 @@ mod test {
 		let parsed = parse_full(&input);
 		assert_eq!(parsed, expected);
 	}
+
+	#[test]
+	fn test_invalid_instructions() {
+		let error = try_parse_full("XRL\n\nAAA = (BBB, CCC)\n").unwrap_err();
+		assert_eq!(
+			error.to_string(),
+			"Invalid instructions at line 1, column 1 (OneOf)"
+		);
+	}
+
+	#[test]
+	fn test_invalid_node() {
+		let error = try_parse_full("RL\n\nAAA = (BBB, CCC)\nBBB = (DDD EEE)\n").unwrap_err();
+		assert_eq!(error.message, "Invalid graph nodes");
+		assert_eq!(error.position, Some((4, 11)));
+	}
 }
@@ -0,0 +1,27 @@
+use common::harness::run_sample;
+
+#[test]
+fn test_sample_a() {
+	assert_eq!(
+		run_sample(
+			env!("CARGO_BIN_EXE_day08"),
+			env!("CARGO_MANIFEST_DIR"),
+			"input_sample_a.txt",
+			&[]
+		),
+		"Single path (Part 1): 2\nMulti-path (Part 2): 2\n"
+	);
+}
+
+#[test]
+fn test_sample_b() {
+	assert_eq!(
+		run_sample(
+			env!("CARGO_BIN_EXE_day08"),
+			env!("CARGO_MANIFEST_DIR"),
+			"input_sample_b.txt",
+			&[]
+		),
+		"Single path (Part 1): 6\nMulti-path (Part 2): 6\n"
+	);
+}
@@ -0,0 +1,42 @@
+use common::Day;
+pub use parse_input::parse_full;
+use sequence::extrapolate;
+
+mod parse_input;
+mod sequence;
+
+#[must_use]
+pub fn extrapolate_all(sequences: &[Vec<i64>], backwards: bool) -> i64 {
+	sequences.iter().map(|l| extrapolate(l, backwards)).sum()
+}
+
+/// Both parts, for running this day alongside the others.
+pub struct Day09;
+
+impl Day for Day09 {
+	fn part1(input: &str) -> String {
+		extrapolate_all(&parse_full(input), false).to_string()
+	}
+
+	fn part2(input: &str) -> String {
+		extrapolate_all(&parse_full(input), true).to_string()
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+	const SAMPLE_INPUT: &str = include_str!("../input_sample.txt");
+
+	#[test]
+	fn test_sample_forwards() {
+		let sequences = parse_full(SAMPLE_INPUT);
+		assert_eq!(extrapolate_all(&sequences, false), 114);
+	}
+
+	#[test]
+	fn test_sample_backwards() {
+		let sequences = parse_full(SAMPLE_INPUT);
+		assert_eq!(extrapolate_all(&sequences, true), 2);
+	}
+}
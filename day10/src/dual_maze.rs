@@ -1,6 +1,7 @@
 use std::collections::HashSet;
 
 use crate::maze::Coords;
+use common::flood_fill;
 
 /// Dual of the other maze, where the main loop acts as a wall instead.
 #[derive(Debug, Clone, Eq, PartialEq)]
@@ -98,16 +99,7 @@ impl DualMaze {
 	#[must_use]
 	pub fn get_enclosed_tiles(&self) -> HashSet<Coords> {
 		// Flood fill the outside of the loop.
-		let mut outside: HashSet<Coords> = HashSet::new();
-		let mut border: Vec<Coords> = vec![(0, 0)];
-		while let Some(node) = border.pop() {
-			// Don't re-visit same node.
-			if outside.contains(&node) {
-				continue;
-			}
-			outside.insert(node);
-			border.extend(self.get_neighbours(node));
-		}
+		let outside = flood_fill((0, 0), |&node| self.get_neighbours(node));
 
 		// Find the tiles inside the loop.
 		let mut inside: HashSet<Coords> = HashSet::new();
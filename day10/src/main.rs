@@ -1,5 +1,6 @@
-use std::io::{self, Read};
+use std::env;
 
+use common::{emit, has_flag, read_input, timed};
 use dual_maze::DualMaze;
 use maze::{get_max_distance, Coords};
 use pretty_maze::pretty_print;
@@ -45,14 +46,12 @@ mod test {
 }
 
 fn main() {
-	let mut input = String::new();
-	io::stdin()
-		.read_to_string(&mut input)
-		.expect("Failed to read input");
+	let input = read_input(env::args().skip(1)).expect("Failed to read input");
 
-	let (distance, area) = count_steps(&input, true);
-	println!("Steps: {distance}");
-	println!("Enclosed area: {area}");
+	let json = has_flag("--json");
+	let show = !json && !has_flag("--quiet");
+	let (distance, area) = timed("Parts 1 and 2", || count_steps(&input, show));
+	emit(("Steps", distance), ("Enclosed area", area), json);
 }
 
 #[cfg(test)]
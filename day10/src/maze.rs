@@ -1,7 +1,6 @@
 use std::{collections::HashSet, fmt, usize};
 
-/// Coords on a 2D grid.
-pub type Coords = (usize, usize);
+pub use common::Coords;
 
 /// Tile with connections.
 #[derive(Debug, Clone, Copy, Eq, Hash, Ord, PartialEq, PartialOrd)]
@@ -81,6 +80,13 @@ impl Maze {
 			.collect()
 	}
 
+	/// Counts the tiles on the loop, i.e. its perimeter.
+	#[cfg(test)]
+	#[must_use]
+	pub fn loop_length(&self) -> usize {
+		self.get_loop_coords().len()
+	}
+
 	/// Finds all tiles on the loop the bunny traces.
 	#[must_use]
 	pub fn get_loop_coords(&self) -> Vec<Coords> {
@@ -247,6 +253,16 @@ mod test {
 
 		use super::*;
 
+		#[test]
+		fn test_loop_length() {
+			assert_eq!(parse_full(SAMPLE_INPUT_SIMPLE_BARE).loop_length(), 8);
+			for input in [SAMPLE_INPUT_COMPLEX_BARE, SAMPLE_INPUT_COMPLEX_CROWDED] {
+				let maze = parse_full(input);
+				assert_eq!(maze.loop_length(), 16);
+				assert_eq!(get_max_distance(&maze.get_loop()), 8);
+			}
+		}
+
 		#[test]
 		fn test_simple() {
 			let maze = parse_full(SAMPLE_INPUT_SIMPLE_BARE);
@@ -1,14 +1,7 @@
 use std::collections::{HashMap, HashSet};
 
 use crate::maze::{get_max_distance, Coords, Maze, Step, Tile};
-
-type AnsiColour = u8;
-
-/// Applies a pretty terminal colour.
-#[must_use]
-pub fn colourise(s: &str, background: AnsiColour, foreground: AnsiColour) -> String {
-	format!("\x1b[38;5;{foreground};48;5;{background}m{s}\x1b[0m")
-}
+use common::pretty::{colourise, AnsiColour};
 
 /// Displays a maze with the given path highlighted.
 pub fn pretty_print(maze: &Maze, path: &[Step], inside: &HashSet<Coords>) {
@@ -0,0 +1,105 @@
+use common::harness::run_sample;
+
+#[test]
+fn test_sample_simple_bare() {
+	assert_eq!(
+		run_sample(
+			env!("CARGO_BIN_EXE_day10"),
+			env!("CARGO_MANIFEST_DIR"),
+			"input_sample_simple_bare.txt",
+			&["--quiet"]
+		),
+		"Steps: 4\nEnclosed area: 1\n"
+	);
+}
+
+#[test]
+fn test_sample_simple_crowded() {
+	assert_eq!(
+		run_sample(
+			env!("CARGO_BIN_EXE_day10"),
+			env!("CARGO_MANIFEST_DIR"),
+			"input_sample_simple_crowded.txt",
+			&["--quiet"]
+		),
+		"Steps: 4\nEnclosed area: 1\n"
+	);
+}
+
+#[test]
+fn test_sample_complex_bare() {
+	assert_eq!(
+		run_sample(
+			env!("CARGO_BIN_EXE_day10"),
+			env!("CARGO_MANIFEST_DIR"),
+			"input_sample_complex_bare.txt",
+			&["--quiet"]
+		),
+		"Steps: 8\nEnclosed area: 1\n"
+	);
+}
+
+#[test]
+fn test_sample_complex_crowded() {
+	assert_eq!(
+		run_sample(
+			env!("CARGO_BIN_EXE_day10"),
+			env!("CARGO_MANIFEST_DIR"),
+			"input_sample_complex_crowded.txt",
+			&["--quiet"]
+		),
+		"Steps: 8\nEnclosed area: 1\n"
+	);
+}
+
+#[test]
+fn test_sample_enclosed_open() {
+	assert_eq!(
+		run_sample(
+			env!("CARGO_BIN_EXE_day10"),
+			env!("CARGO_MANIFEST_DIR"),
+			"input_sample_enclosed_open.txt",
+			&["--quiet"]
+		),
+		"Steps: 23\nEnclosed area: 4\n"
+	);
+}
+
+#[test]
+fn test_sample_enclosed_narrow() {
+	assert_eq!(
+		run_sample(
+			env!("CARGO_BIN_EXE_day10"),
+			env!("CARGO_MANIFEST_DIR"),
+			"input_sample_enclosed_narrow.txt",
+			&["--quiet"]
+		),
+		"Steps: 22\nEnclosed area: 4\n"
+	);
+}
+
+#[test]
+fn test_sample_enclosed_medium() {
+	assert_eq!(
+		run_sample(
+			env!("CARGO_BIN_EXE_day10"),
+			env!("CARGO_MANIFEST_DIR"),
+			"input_sample_enclosed_medium.txt",
+			&["--quiet"]
+		),
+		"Steps: 70\nEnclosed area: 8\n"
+	);
+}
+
+#[test]
+fn test_sample_enclosed_crowded() {
+	assert_eq!(
+		run_sample(
+			env!("CARGO_BIN_EXE_day10"),
+			env!("CARGO_MANIFEST_DIR"),
+			"input_sample_enclosed_crowded.txt",
+			&["--quiet"]
+		),
+		"Steps: 80\nEnclosed area: 10\n"
+	);
+}
@@ -1,5 +1,6 @@
-use std::io::{self, Read};
+use std::env;
 
+use common::{emit, has_flag, read_input, timed};
 use parse_input::parse_full;
 
 mod parse_input;
@@ -34,14 +35,23 @@ mod test {
 }
 
 fn main() {
-	let mut input = String::new();
-	io::stdin()
-		.read_to_string(&mut input)
-		.expect("Failed to read input");
-
-	println!("Expand by 2: {}", get_sum_distances(&input, 2));
-	println!(
-		"Expand by a million: {}",
-		get_sum_distances(&input, 1_000_000)
+	let input = read_input(env::args().skip(1)).expect("Failed to read input");
+
+	let json = has_flag("--json");
+	if !json {
+		let (empty_rows, empty_cols) = parse_full(&input).empty_axes();
+		println!("Empty rows: {empty_rows}, empty columns: {empty_cols}");
+	}
+
+	emit(
+		(
+			"Expand by 2",
+			timed("Part 1", || get_sum_distances(&input, 2)),
+		),
+		(
+			"Expand by a million",
+			timed("Part 2", || get_sum_distances(&input, 1_000_000)),
+		),
+		json,
 	);
 }
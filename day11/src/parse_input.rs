@@ -10,12 +10,14 @@ pub fn parse_full(input: &str) -> Starfield {
 	let mut nb_galaxies = 0;
 	let mut nb_per_row: DimCount = BTreeMap::new();
 	let mut nb_per_col: DimCount = BTreeMap::new();
+	let mut galaxies = Vec::new();
 	for (row, line) in input.lines().enumerate() {
 		for (col, ch) in line.chars().enumerate() {
 			if ch == '#' {
 				nb_galaxies += 1;
 				*nb_per_row.entry(row).or_insert(0) += 1;
 				*nb_per_col.entry(col).or_insert(0) += 1;
+				galaxies.push((row, col));
 			}
 		}
 	}
@@ -23,6 +25,7 @@ pub fn parse_full(input: &str) -> Starfield {
 		nb_galaxies,
 		nb_per_row,
 		nb_per_col,
+		galaxies,
 	}
 }
 
@@ -38,7 +41,40 @@ mod test {
 			nb_galaxies: 4,
 			nb_per_row: BTreeMap::from([(0, 1), (1, 1), (2, 2)]),
 			nb_per_col: BTreeMap::from([(0, 2), (1, 1), (3, 1)]),
+			galaxies: vec![(0, 0), (1, 1), (2, 0), (2, 3)],
 		};
 		assert_eq!(starfield, expected);
 	}
+
+	#[test]
+	fn test_round_trip_sample() {
+		let input = include_str!("../input_sample.txt");
+		let starfield = parse_full(input);
+		let expected = vec![
+			(0, 3),
+			(1, 7),
+			(2, 0),
+			(4, 6),
+			(5, 1),
+			(6, 9),
+			(8, 7),
+			(9, 0),
+			(9, 4),
+		];
+		assert_eq!(starfield.to_coords(), expected);
+	}
+
+	#[test]
+	fn test_empty_axes_sample() {
+		let input = include_str!("../input_sample.txt");
+		let starfield = parse_full(input);
+		assert_eq!(starfield.empty_axes(), (2, 3));
+	}
+
+	#[test]
+	fn test_density_sample() {
+		let input = include_str!("../input_sample.txt");
+		let starfield = parse_full(input);
+		assert!((starfield.density(10, 10) - 0.09).abs() < 1e-12);
+	}
 }
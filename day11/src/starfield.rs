@@ -1,6 +1,14 @@
 use std::collections::BTreeMap;
 
 pub type DimCount = BTreeMap<usize, usize>;
+pub type Coords = (usize, usize);
+
+/// Reasons space can't be expanded.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum ExpandError {
+	/// Expanding by `0` would shrink empty space to nothing.
+	ZeroFactor,
+}
 
 /// List of sorted galaxy positions.
 #[derive(Debug, Clone, Eq, PartialEq)]
@@ -8,44 +16,150 @@ pub struct Starfield {
 	pub nb_galaxies: usize,
 	pub nb_per_row: DimCount,
 	pub nb_per_col: DimCount,
+	pub galaxies: Vec<Coords>,
 }
 
 impl Starfield {
 	/// Adjusts distances so unoccupied rows and columns are twice as wide.
+	/// # Panics
+	/// If `expand_factor` is `0`.
 	#[must_use]
 	pub fn expand(&self, expand_factor: usize) -> Starfield {
-		let rows = expand_axis_distances(&self.nb_per_row, expand_factor);
-		let cols = expand_axis_distances(&self.nb_per_col, expand_factor);
-		Starfield {
+		self.try_expand(expand_factor)
+			.expect("Expand factor must be at least 1")
+	}
+
+	/// Expands space, rejecting factors that would shrink it.
+	/// # Errors
+	/// If `expand_factor` is `0`.
+	pub fn try_expand(&self, expand_factor: usize) -> Result<Starfield, ExpandError> {
+		if expand_factor == 0 {
+			return Err(ExpandError::ZeroFactor);
+		}
+		let row_mapping = expand_axis_mapping(&self.nb_per_row, expand_factor);
+		let col_mapping = expand_axis_mapping(&self.nb_per_col, expand_factor);
+		Ok(Starfield {
 			nb_galaxies: self.nb_galaxies,
-			nb_per_row: rows,
-			nb_per_col: cols,
+			nb_per_row: expand_axis_distances(&self.nb_per_row, &row_mapping),
+			nb_per_col: expand_axis_distances(&self.nb_per_col, &col_mapping),
+			galaxies: self
+				.galaxies
+				.iter()
+				.map(|(row, col)| (row_mapping[row], col_mapping[col]))
+				.collect(),
+		})
+	}
+
+	/// Lists galaxy positions, sorted by row then column.
+	#[cfg(test)]
+	#[must_use]
+	pub fn to_coords(&self) -> Vec<Coords> {
+		self.galaxies.clone()
+	}
+
+	/// Gets the proportion of cells holding a galaxy, given the unexpanded grid size.
+	#[cfg(test)]
+	#[must_use]
+	pub fn density(&self, width: usize, height: usize) -> f64 {
+		let nb_cells = width * height;
+		if nb_cells == 0 {
+			0.
+		} else {
+			#[allow(clippy::cast_precision_loss)]
+			let density = self.nb_galaxies as f64 / nb_cells as f64;
+			density
 		}
 	}
 
+	/// Counts unoccupied rows and columns within the bounding box of the galaxies.
+	#[must_use]
+	pub fn empty_axes(&self) -> (usize, usize) {
+		(
+			count_empty_on_axis(&self.nb_per_row),
+			count_empty_on_axis(&self.nb_per_col),
+		)
+	}
+
 	/// Adds together all distances between pairs of galaxies.
+	/// # Panics
+	/// If the sum doesn't fit in an `i64`.
 	#[must_use]
 	pub fn get_sum_distances(&self) -> i64 {
-		get_sum_axis_distances(self.nb_galaxies, &self.nb_per_row)
-			+ get_sum_axis_distances(self.nb_galaxies, &self.nb_per_col)
+		i64::try_from(self.get_sum_distances_i128()).expect("Sum too big")
+	}
+
+	/// Adds together all distances between pairs of galaxies, without overflowing on huge fields.
+	#[must_use]
+	pub fn get_sum_distances_i128(&self) -> i128 {
+		get_sum_axis_distances_i128(self.nb_galaxies, &self.nb_per_row)
+			+ get_sum_axis_distances_i128(self.nb_galaxies, &self.nb_per_col)
 	}
 }
 
-/// Expands space along one dimension.
+/// Maps each occupied coordinate along one dimension to its expanded value.
 #[must_use]
-fn expand_axis_distances(coords: &DimCount, expand_factor: usize) -> DimCount {
+fn expand_axis_mapping(coords: &DimCount, expand_factor: usize) -> BTreeMap<usize, usize> {
 	coords
-		.iter()
+		.keys()
 		.enumerate()
-		.map(|(occupied_index, (&coord, &nb_galaxies))| {
-			(
-				expand_distance(coord, occupied_index, expand_factor),
-				nb_galaxies,
-			)
+		.map(|(occupied_index, &coord)| {
+			(coord, expand_distance(coord, occupied_index, expand_factor))
 		})
 		.collect()
 }
 
+/// Expands space along one dimension.
+#[must_use]
+fn expand_axis_distances(coords: &DimCount, mapping: &BTreeMap<usize, usize>) -> DimCount {
+	coords
+		.iter()
+		.map(|(coord, &nb_galaxies)| (mapping[coord], nb_galaxies))
+		.collect()
+}
+
+/// Adds together the straight-line distances between all pairs of points.
+#[cfg(test)]
+#[must_use]
+pub fn get_sum_euclidean(points: &[Coords]) -> f64 {
+	let mut total = 0.;
+	for (index, &(row_a, col_a)) in points.iter().enumerate() {
+		for &(row_b, col_b) in &points[index + 1..] {
+			#[allow(clippy::cast_precision_loss)]
+			let row_diff = row_a.abs_diff(row_b) as f64;
+			#[allow(clippy::cast_precision_loss)]
+			let col_diff = col_a.abs_diff(col_b) as f64;
+			total += row_diff.hypot(col_diff);
+		}
+	}
+	total
+}
+
+/// Finds the indices of the two nearest points and their taxicab distance.
+/// Ties are broken in favour of the earliest pair.
+#[cfg(test)]
+#[must_use]
+pub fn closest_pair(points: &[Coords]) -> Option<((usize, usize), usize)> {
+	let mut closest: Option<((usize, usize), usize)> = None;
+	for (index_a, &(row_a, col_a)) in points.iter().enumerate() {
+		for (index_b, &(row_b, col_b)) in points.iter().enumerate().skip(index_a + 1) {
+			let distance = row_a.abs_diff(row_b) + col_a.abs_diff(col_b);
+			if closest.is_none_or(|(_, best)| distance < best) {
+				closest = Some(((index_a, index_b), distance));
+			}
+		}
+	}
+	closest
+}
+
+/// Counts unoccupied coordinates between the first and last occupied ones.
+#[must_use]
+fn count_empty_on_axis(coords: &DimCount) -> usize {
+	match (coords.keys().next(), coords.keys().next_back()) {
+		(Some(first), Some(last)) => last - first + 1 - coords.len(),
+		_ => 0,
+	}
+}
+
 /// Expands space between `0` and `index`.
 #[must_use]
 fn expand_distance(coord: usize, occupied_index: usize, expand_factor: usize) -> usize {
@@ -53,14 +167,14 @@ fn expand_distance(coord: usize, occupied_index: usize, expand_factor: usize) ->
 	coord + (expand_factor - 1) * count_unoccupied
 }
 
-/// Adds together all distances between pairs of galaxies along one dimension.
+/// Adds together all distances between pairs of galaxies along one dimension, accumulating in `i128`.
 #[must_use]
-pub fn get_sum_axis_distances(nb_galaxies: usize, coords: &DimCount) -> i64 {
+pub fn get_sum_axis_distances_i128(nb_galaxies: usize, coords: &DimCount) -> i128 {
 	let mut total = 0;
-	let mut weight = 1 - i64::try_from(nb_galaxies).expect("Too many galaxies");
+	let mut weight = 1 - i128::try_from(nb_galaxies).expect("Too many galaxies");
 	for (&coord, &nb_at_coord) in coords {
 		for _ in 0..nb_at_coord {
-			total += weight * i64::try_from(coord).expect("Grid too big");
+			total += weight * i128::try_from(coord).expect("Grid too big");
 			weight += 2;
 		}
 	}
@@ -77,11 +191,13 @@ mod test_expand {
 			nb_galaxies: 0,
 			nb_per_row: BTreeMap::from([]),
 			nb_per_col: BTreeMap::from([]),
+			galaxies: vec![],
 		};
 		let expected = Starfield {
 			nb_galaxies: 0,
 			nb_per_row: BTreeMap::from([]),
 			nb_per_col: BTreeMap::from([]),
+			galaxies: vec![],
 		};
 		assert_eq!(starfield.expand(2), expected);
 	}
@@ -92,11 +208,13 @@ mod test_expand {
 			nb_galaxies: 4,
 			nb_per_row: BTreeMap::from([(0, 2), (1, 2)]),
 			nb_per_col: BTreeMap::from([(0, 2), (1, 2)]),
+			galaxies: vec![(0, 0), (0, 1), (1, 0), (1, 1)],
 		};
 		let expected = Starfield {
 			nb_galaxies: 4,
 			nb_per_row: BTreeMap::from([(0, 2), (1, 2)]),
 			nb_per_col: BTreeMap::from([(0, 2), (1, 2)]),
+			galaxies: vec![(0, 0), (0, 1), (1, 0), (1, 1)],
 		};
 		assert_eq!(starfield.expand(2), expected);
 	}
@@ -107,11 +225,13 @@ mod test_expand {
 			nb_galaxies: 2,
 			nb_per_row: BTreeMap::from([(0, 1), (1, 1)]),
 			nb_per_col: BTreeMap::from([(0, 1), (1, 1)]),
+			galaxies: vec![(0, 0), (1, 1)],
 		};
 		let expected = Starfield {
 			nb_galaxies: 2,
 			nb_per_row: BTreeMap::from([(0, 1), (1, 1)]),
 			nb_per_col: BTreeMap::from([(0, 1), (1, 1)]),
+			galaxies: vec![(0, 0), (1, 1)],
 		};
 		assert_eq!(starfield.expand(2), expected);
 	}
@@ -122,11 +242,13 @@ mod test_expand {
 			nb_galaxies: 2,
 			nb_per_row: BTreeMap::from([(0, 1), (2, 1)]),
 			nb_per_col: BTreeMap::from([(0, 2)]),
+			galaxies: vec![(0, 0), (2, 0)],
 		};
 		let expected = Starfield {
 			nb_galaxies: 2,
 			nb_per_row: BTreeMap::from([(0, 1), (3, 1)]),
 			nb_per_col: BTreeMap::from([(0, 2)]),
+			galaxies: vec![(0, 0), (3, 0)],
 		};
 		assert_eq!(starfield.expand(2), expected);
 	}
@@ -137,11 +259,13 @@ mod test_expand {
 			nb_galaxies: 2,
 			nb_per_row: BTreeMap::from([(0, 2)]),
 			nb_per_col: BTreeMap::from([(0, 1), (2, 1)]),
+			galaxies: vec![(0, 0), (0, 2)],
 		};
 		let expected = Starfield {
 			nb_galaxies: 2,
 			nb_per_row: BTreeMap::from([(0, 2)]),
 			nb_per_col: BTreeMap::from([(0, 1), (3, 1)]),
+			galaxies: vec![(0, 0), (0, 3)],
 		};
 		assert_eq!(starfield.expand(2), expected);
 	}
@@ -152,11 +276,13 @@ mod test_expand {
 			nb_galaxies: 2,
 			nb_per_row: BTreeMap::from([(0, 1), (2, 1)]),
 			nb_per_col: BTreeMap::from([(0, 1), (2, 1)]),
+			galaxies: vec![(0, 0), (2, 2)],
 		};
 		let expected = Starfield {
 			nb_galaxies: 2,
 			nb_per_row: BTreeMap::from([(0, 1), (3, 1)]),
 			nb_per_col: BTreeMap::from([(0, 1), (3, 1)]),
+			galaxies: vec![(0, 0), (3, 3)],
 		};
 		assert_eq!(starfield.expand(2), expected);
 	}
@@ -167,11 +293,13 @@ mod test_expand {
 			nb_galaxies: 2,
 			nb_per_row: BTreeMap::from([(0, 2)]),
 			nb_per_col: BTreeMap::from([(0, 1), (11, 1)]),
+			galaxies: vec![(0, 0), (0, 11)],
 		};
 		let expected = Starfield {
 			nb_galaxies: 2,
 			nb_per_row: BTreeMap::from([(0, 2)]),
 			nb_per_col: BTreeMap::from([(0, 1), (21, 1)]),
+			galaxies: vec![(0, 0), (0, 21)],
 		};
 		assert_eq!(starfield.expand(2), expected);
 	}
@@ -182,6 +310,7 @@ mod test_expand {
 			nb_galaxies: 4,
 			nb_per_row: BTreeMap::from([(0, 1), (3, 1), (4, 1), (6, 1)]),
 			nb_per_col: BTreeMap::from([(0, 4)]),
+			galaxies: vec![(0, 0), (3, 0), (4, 0), (6, 0)],
 		};
 		// 0 1 2 3 4 5 6
 		// *     * *   *
@@ -190,6 +319,7 @@ mod test_expand {
 			nb_galaxies: 4,
 			nb_per_row: BTreeMap::from([(0, 1), (5, 1), (6, 1), (9, 1)]),
 			nb_per_col: BTreeMap::from([(0, 4)]),
+			galaxies: vec![(0, 0), (5, 0), (6, 0), (9, 0)],
 		};
 		assert_eq!(starfield.expand(2), expected);
 	}
@@ -200,16 +330,74 @@ mod test_expand {
 			nb_galaxies: 2,
 			nb_per_row: BTreeMap::from([(0, 2)]),
 			nb_per_col: BTreeMap::from([(0, 1), (2, 1)]),
+			galaxies: vec![(0, 0), (0, 2)],
 		};
 		let expected = Starfield {
 			nb_galaxies: 2,
 			nb_per_row: BTreeMap::from([(0, 2)]),
 			nb_per_col: BTreeMap::from([(0, 1), (101, 1)]),
+			galaxies: vec![(0, 0), (0, 101)],
 		};
 		assert_eq!(starfield.expand(100), expected);
 	}
 }
 
+#[cfg(test)]
+mod test_try_expand {
+	use super::*;
+
+	#[test]
+	#[should_panic(expected = "Expand factor must be at least 1")]
+	fn test_expand_zero() {
+		let starfield = Starfield {
+			nb_galaxies: 1,
+			nb_per_row: BTreeMap::from([(0, 1)]),
+			nb_per_col: BTreeMap::from([(0, 1)]),
+			galaxies: vec![(0, 0)],
+		};
+		let _ = starfield.expand(0);
+	}
+
+	#[test]
+	fn test_zero() {
+		let starfield = Starfield {
+			nb_galaxies: 2,
+			nb_per_row: BTreeMap::from([(0, 1), (2, 1)]),
+			nb_per_col: BTreeMap::from([(0, 1), (2, 1)]),
+			galaxies: vec![(0, 0), (2, 2)],
+		};
+		assert_eq!(starfield.try_expand(0), Err(ExpandError::ZeroFactor));
+	}
+
+	#[test]
+	fn test_one() {
+		let starfield = Starfield {
+			nb_galaxies: 2,
+			nb_per_row: BTreeMap::from([(0, 1), (2, 1)]),
+			nb_per_col: BTreeMap::from([(0, 1), (2, 1)]),
+			galaxies: vec![(0, 0), (2, 2)],
+		};
+		assert_eq!(starfield.try_expand(1), Ok(starfield.clone()));
+	}
+
+	#[test]
+	fn test_two() {
+		let starfield = Starfield {
+			nb_galaxies: 2,
+			nb_per_row: BTreeMap::from([(0, 1), (2, 1)]),
+			nb_per_col: BTreeMap::from([(0, 1), (2, 1)]),
+			galaxies: vec![(0, 0), (2, 2)],
+		};
+		let expected = Starfield {
+			nb_galaxies: 2,
+			nb_per_row: BTreeMap::from([(0, 1), (3, 1)]),
+			nb_per_col: BTreeMap::from([(0, 1), (3, 1)]),
+			galaxies: vec![(0, 0), (3, 3)],
+		};
+		assert_eq!(starfield.try_expand(2), Ok(expected));
+	}
+}
+
 #[cfg(test)]
 mod test_get_sum_distances {
 	use super::*;
@@ -220,6 +408,7 @@ mod test_get_sum_distances {
 			nb_galaxies: 0,
 			nb_per_row: BTreeMap::from([]),
 			nb_per_col: BTreeMap::from([]),
+			galaxies: vec![],
 		};
 		assert_eq!(starfield.get_sum_distances(), 0);
 	}
@@ -230,6 +419,7 @@ mod test_get_sum_distances {
 			nb_galaxies: 4,
 			nb_per_row: BTreeMap::from([(0, 2), (1, 2)]),
 			nb_per_col: BTreeMap::from([(0, 2), (1, 2)]),
+			galaxies: vec![(0, 0), (0, 1), (1, 0), (1, 1)],
 		};
 		//  01
 		// 0**
@@ -243,6 +433,7 @@ mod test_get_sum_distances {
 			nb_galaxies: 2,
 			nb_per_row: BTreeMap::from([(0, 1), (1, 1)]),
 			nb_per_col: BTreeMap::from([(0, 1), (1, 1)]),
+			galaxies: vec![(0, 0), (1, 1)],
 		};
 		//  01
 		// 0*.
@@ -256,6 +447,7 @@ mod test_get_sum_distances {
 			nb_galaxies: 2,
 			nb_per_row: BTreeMap::from([(0, 1), (2, 1)]),
 			nb_per_col: BTreeMap::from([(0, 2)]),
+			galaxies: vec![(0, 0), (2, 0)],
 		};
 		// 012
 		// *.*
@@ -268,6 +460,7 @@ mod test_get_sum_distances {
 			nb_galaxies: 2,
 			nb_per_row: BTreeMap::from([(0, 2)]),
 			nb_per_col: BTreeMap::from([(0, 1), (2, 1)]),
+			galaxies: vec![(0, 0), (0, 2)],
 		};
 		assert_eq!(starfield.get_sum_distances(), 2);
 	}
@@ -278,6 +471,7 @@ mod test_get_sum_distances {
 			nb_galaxies: 2,
 			nb_per_row: BTreeMap::from([(0, 1), (2, 1)]),
 			nb_per_col: BTreeMap::from([(0, 1), (2, 1)]),
+			galaxies: vec![(0, 0), (2, 2)],
 		};
 		//  012
 		// 0*..
@@ -292,6 +486,7 @@ mod test_get_sum_distances {
 			nb_galaxies: 2,
 			nb_per_row: BTreeMap::from([(0, 2)]),
 			nb_per_col: BTreeMap::from([(0, 1), (11, 1)]),
+			galaxies: vec![(0, 0), (0, 11)],
 		};
 		// 012345678901
 		// *..........*
@@ -304,6 +499,7 @@ mod test_get_sum_distances {
 			nb_galaxies: 3,
 			nb_per_row: BTreeMap::from([(0, 2), (1, 1)]),
 			nb_per_col: BTreeMap::from([(0, 1), (1, 1), (2, 1)]),
+			galaxies: vec![(0, 0), (0, 2), (1, 1)],
 		};
 		//  012
 		// 0*.*
@@ -311,3 +507,89 @@ mod test_get_sum_distances {
 		assert_eq!(starfield.get_sum_distances(), 6);
 	}
 }
+
+#[cfg(test)]
+mod test_get_sum_distances_i128 {
+	use super::*;
+
+	#[test]
+	fn test_matches_i64() {
+		let starfield = Starfield {
+			nb_galaxies: 3,
+			nb_per_row: BTreeMap::from([(0, 2), (1, 1)]),
+			nb_per_col: BTreeMap::from([(0, 1), (1, 1), (2, 1)]),
+			galaxies: vec![(0, 0), (0, 2), (1, 1)],
+		};
+		assert_eq!(starfield.get_sum_distances_i128(), 6);
+	}
+
+	#[test]
+	fn test_huge() {
+		let far = usize::try_from(i64::MAX).unwrap();
+		let starfield = Starfield {
+			nb_galaxies: 3,
+			nb_per_row: BTreeMap::from([(0, 3)]),
+			nb_per_col: BTreeMap::from([(0, 1), (far, 2)]),
+			galaxies: vec![(0, 0), (0, far), (0, far)],
+		};
+		// 0 to far twice, far to far once
+		assert_eq!(starfield.get_sum_distances_i128(), 2 * i128::from(i64::MAX));
+	}
+}
+
+#[cfg(test)]
+mod test_get_sum_euclidean {
+	use super::*;
+
+	#[test]
+	fn test_trivial() {
+		assert!(get_sum_euclidean(&[]).abs() < 1e-12);
+	}
+
+	#[test]
+	fn test_diagonal() {
+		let total = get_sum_euclidean(&[(0, 0), (1, 1)]);
+		assert!((total - 2_f64.sqrt()).abs() < 1e-12);
+	}
+
+	#[test]
+	fn test_triangle() {
+		let total = get_sum_euclidean(&[(0, 0), (0, 3), (4, 0)]);
+		assert!((total - 12.).abs() < 1e-12);
+	}
+}
+
+#[cfg(test)]
+mod test_closest_pair {
+	use super::*;
+
+	#[test]
+	fn test_trivial() {
+		assert_eq!(closest_pair(&[]), None);
+		assert_eq!(closest_pair(&[(3, 4)]), None);
+	}
+
+	#[test]
+	fn test_unambiguous() {
+		//  0123456
+		// 0*.....*
+		// 1.......
+		// 2....*..
+		// 3.....*.
+		let points = [(0, 0), (0, 6), (2, 4), (3, 5)];
+		assert_eq!(closest_pair(&points), Some(((2, 3), 2)));
+	}
+
+	#[test]
+	fn test_expanded() {
+		let starfield = Starfield {
+			nb_galaxies: 3,
+			nb_per_row: BTreeMap::from([(0, 2), (2, 1)]),
+			nb_per_col: BTreeMap::from([(0, 2), (3, 1)]),
+			galaxies: vec![(0, 0), (0, 3), (2, 0)],
+		};
+		let expanded = starfield.expand(2);
+		assert_eq!(expanded.galaxies, vec![(0, 0), (0, 5), (3, 0)]);
+		assert_eq!(closest_pair(&expanded.to_coords()), Some(((0, 2), 3)));
+	}
+}
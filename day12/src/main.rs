@@ -1,5 +1,6 @@
-use std::io::{self, Read};
+use std::env;
 
+use common::{emit, has_flag, read_input, timed};
 use picross::PointRow;
 
 use crate::parse_input::parse_full;
@@ -7,14 +8,31 @@ use crate::parse_input::parse_full;
 mod parse_input;
 mod picross;
 
+#[must_use]
+fn row_counts(rows: &[PointRow]) -> Vec<usize> {
+	rows.iter().map(PointRow::get_arrangements_count).collect()
+}
+
 #[must_use]
 fn get_total_arrangements(rows: &[PointRow]) -> usize {
-	rows.iter().map(PointRow::get_arrangements_count).sum()
+	row_counts(rows).into_iter().sum()
+}
+
+/// Counts arrangements on several threads, splitting rows into one chunk per available core.
+#[cfg(feature = "parallel")]
+#[must_use]
+fn get_total_arrangements_parallel(rows: &[PointRow]) -> usize {
+	common::map_chunks(rows, get_total_arrangements)
+		.into_iter()
+		.sum()
 }
 
 #[must_use]
 fn get_total_unfolded_arrangements(rows: &[PointRow]) -> usize {
 	let unfolded: Vec<PointRow> = rows.iter().map(PointRow::unfold).collect();
+	#[cfg(feature = "parallel")]
+	return get_total_arrangements_parallel(&unfolded);
+	#[cfg(not(feature = "parallel"))]
 	get_total_arrangements(&unfolded)
 }
 
@@ -34,27 +52,70 @@ mod test {
 		assert_eq!(counts, expected);
 	}
 
+	#[test]
+	fn test_sample_row_counts() {
+		let rows = parse_full(SAMPLE_INPUT);
+		assert_eq!(row_counts(&rows), vec![1, 4, 1, 1, 4, 10]);
+	}
+
 	#[test]
 	fn test_sample_folded() {
 		assert_eq!(get_total_arrangements(&parse_full(SAMPLE_INPUT)), 21);
 	}
 
-	// #[test]
-	// fn test_sample_unfolded() {
-	// 	assert_eq!(
-	// 		get_total_unfolded_arrangements(&parse_full(SAMPLE_INPUT)),
-	// 		525152
-	// 	);
-	// }
+	#[test]
+	fn test_sample_lines_brute_force() {
+		let rows = parse_full(SAMPLE_INPUT);
+		for row in rows {
+			assert_eq!(
+				row.get_arrangements_count(),
+				row.get_arrangements_count_brute_force()
+			);
+		}
+	}
+
+	#[test]
+	fn test_sample_unfolded() {
+		assert_eq!(
+			get_total_unfolded_arrangements(&parse_full(SAMPLE_INPUT)),
+			525_152
+		);
+	}
+
+	#[cfg(feature = "parallel")]
+	#[test]
+	fn test_sample_unfolded_parallel() {
+		let unfolded: Vec<PointRow> = parse_full(SAMPLE_INPUT)
+			.iter()
+			.map(PointRow::unfold)
+			.collect();
+		assert_eq!(get_total_arrangements_parallel(&unfolded), 525_152);
+		assert_eq!(
+			get_total_arrangements_parallel(&unfolded),
+			get_total_arrangements(&unfolded)
+		);
+	}
 }
 
 fn main() {
-	let mut input = String::new();
-	io::stdin()
-		.read_to_string(&mut input)
-		.expect("Failed to read input");
+	let input = read_input(env::args().skip(1)).expect("Failed to read input");
 
 	let folded = parse_full(&input);
-	println!("Folded: {}", get_total_arrangements(&folded));
-	println!("Unfolded: {}", get_total_unfolded_arrangements(&folded));
+	let json = has_flag("--json");
+	if !json && has_flag("--per-row") {
+		for (index, count) in row_counts(&folded).into_iter().enumerate() {
+			println!("Row {}: {}", index + 1, count);
+		}
+	}
+	emit(
+		(
+			"Folded",
+			timed("Part 1", || get_total_arrangements(&folded)),
+		),
+		(
+			"Unfolded",
+			timed("Part 2", || get_total_unfolded_arrangements(&folded)),
+		),
+		json,
+	);
 }
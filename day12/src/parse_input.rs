@@ -1,6 +1,7 @@
 use nom::{
 	bytes::complete::tag,
-	character::complete::{multispace1, one_of, space1, u16},
+	character::complete::{anychar, space1, u16},
+	combinator::{all_consuming, map_opt},
 	multi::{many1, separated_list1},
 	IResult,
 };
@@ -9,14 +10,7 @@ use crate::picross::{PointRow, PointState};
 
 /// Consumes one point on the row.
 fn point_state(input: &str) -> IResult<&str, PointState> {
-	let (input, point) = one_of(".#?")(input)?;
-	let point = match point {
-		'.' => PointState::Working,
-		'#' => PointState::Broken,
-		'?' => PointState::Unknown,
-		_ => unreachable!(),
-	};
-	Ok((input, point))
+	map_opt(anychar, PointState::from_char)(input)
 }
 
 /// Consumes group numbers.
@@ -26,12 +20,11 @@ fn groups(input: &str) -> IResult<&str, Vec<usize>> {
 	Ok((input, groups))
 }
 
-/// Consumes a whole row.
-fn point_row(input: &str) -> IResult<&str, PointRow> {
+/// Consumes a row, up to the end of the line.
+fn point_row_line(input: &str) -> IResult<&str, PointRow> {
 	let (input, points) = many1(point_state)(input)?;
 	let (input, _) = space1(input)?;
 	let (input, groups) = groups(input)?;
-	let (input, _) = multispace1(input)?;
 	Ok((input, PointRow { points, groups }))
 }
 
@@ -40,14 +33,44 @@ fn point_row(input: &str) -> IResult<&str, PointRow> {
 /// On any parse error.
 #[must_use]
 pub fn parse_full(input: &str) -> Vec<PointRow> {
-	let (_, point_rows) = many1(point_row)(input).expect("Parse error");
-	point_rows
+	try_parse_full(input).unwrap_or_else(|error| panic!("Parse error: {error:?}"))
+}
+
+/// Reasons the input can't be read as rows.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum ParseError {
+	/// The line with this 1-based number isn't a valid row.
+	InvalidRow(usize),
+	/// The groups on the line with this 1-based number can't fit in its points.
+	OverconstrainedRow(usize),
+}
+
+/// Parses the whole input, checking each row could have at least one arrangement.
+/// # Errors
+/// On a malformed row, or one whose groups and the gaps between them are longer than the row.
+pub fn try_parse_full(input: &str) -> Result<Vec<PointRow>, ParseError> {
+	input
+		.lines()
+		.enumerate()
+		.filter(|(_, line)| !line.is_empty())
+		.map(|(index, line)| {
+			let line_number = index + 1;
+			let (_, row) = all_consuming(point_row_line)(line)
+				.map_err(|_| ParseError::InvalidRow(line_number))?;
+			let min_len = row.groups.iter().sum::<usize>() + row.groups.len().saturating_sub(1);
+			if min_len > row.points.len() {
+				Err(ParseError::OverconstrainedRow(line_number))
+			} else {
+				Ok(row)
+			}
+		})
+		.collect()
 }
 
 #[cfg(test)]
 mod test {
 	use super::*;
-	use crate::picross::PointState::{Broken, Unknown, Working};
+	use crate::picross::PointState::{Broken, ForcedBroken, Unknown, Working};
 
 	#[test]
 	fn test_parse() {
@@ -64,4 +87,61 @@ mod test {
 		];
 		assert_eq!(parse_full(input), expected);
 	}
+
+	#[test]
+	fn test_parse_forced_broken() {
+		let input = "!?. 1\n";
+		let expected = vec![PointRow {
+			points: vec![ForcedBroken, Unknown, Working],
+			groups: vec![1],
+		}];
+		assert_eq!(parse_full(input), expected);
+	}
+
+	#[test]
+	fn test_try_parse() {
+		let input = "#.? 1,1\n? 1";
+		let expected = vec![
+			PointRow {
+				points: vec![Broken, Working, Unknown],
+				groups: vec![1, 1],
+			},
+			PointRow {
+				points: vec![Unknown],
+				groups: vec![1],
+			},
+		];
+		assert_eq!(try_parse_full(input), Ok(expected));
+	}
+
+	#[test]
+	#[should_panic(expected = "OverconstrainedRow(1)")]
+	fn test_parse_overconstrained() {
+		let _ = parse_full("??? 1,2\n");
+	}
+
+	#[test]
+	fn test_try_parse_bad_char() {
+		let input = "#.? 1,1\n?x 1\n";
+		assert_eq!(try_parse_full(input), Err(ParseError::InvalidRow(2)));
+	}
+
+	#[test]
+	fn test_try_parse_overconstrained() {
+		let input = "??? 1,2\n";
+		assert_eq!(
+			try_parse_full(input),
+			Err(ParseError::OverconstrainedRow(1))
+		);
+	}
+
+	#[test]
+	fn test_try_parse_just_fits() {
+		let input = "???? 1,2\n";
+		let expected = vec![PointRow {
+			points: vec![Unknown; 4],
+			groups: vec![1, 2],
+		}];
+		assert_eq!(try_parse_full(input), Ok(expected));
+	}
 }
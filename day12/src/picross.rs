@@ -1,9 +1,30 @@
+use std::collections::HashMap;
+
+/// Memo of arrangement counts, keyed on point index, group index, and length of the current run of broken points.
+type ArrangementsMemo = HashMap<(usize, usize, usize), usize>;
+
 /// State of a point along a row.
 #[derive(Debug, Clone, Copy, Eq, Hash, Ord, PartialEq, PartialOrd)]
 pub enum PointState {
 	Working,
 	Broken,
 	Unknown,
+	/// Broken, but written as a wildcard rather than a known point.
+	ForcedBroken,
+}
+
+impl PointState {
+	/// Reads a point from its puzzle notation.
+	#[must_use]
+	pub fn from_char(ch: char) -> Option<PointState> {
+		match ch {
+			'.' => Some(PointState::Working),
+			'#' => Some(PointState::Broken),
+			'?' => Some(PointState::Unknown),
+			'!' => Some(PointState::ForcedBroken),
+			_ => None,
+		}
+	}
 }
 
 /// Row of points with partial information
@@ -17,20 +38,108 @@ impl PointRow {
 	/// Grows into five copies of itself.
 	#[must_use]
 	pub fn unfold(&self) -> PointRow {
-		let mut points = self.points.clone();
-		points.push(PointState::Unknown); // FIXME: oops extra at end
-		let mut points = points.repeat(5);
-		points.pop();
+		self.unfold_by(5)
+	}
 
-		let groups = self.groups.repeat(5);
+	/// Grows into `nb_copies` copies of itself, separated by unknown points.
+	#[must_use]
+	pub fn unfold_by(&self, nb_copies: usize) -> PointRow {
+		let points = vec![self.points.clone(); nb_copies].join(&PointState::Unknown);
+		let groups = self.groups.repeat(nb_copies);
 
 		PointRow { points, groups }
 	}
 
+	/// Gets the number of unknown points and the total number of broken points in the groups,
+	/// as a rough measure of how hard the row is.
+	#[cfg(test)]
+	#[must_use]
+	pub fn complexity(&self) -> (usize, usize) {
+		let nb_unknown = self
+			.points
+			.iter()
+			.filter(|&&point| point == PointState::Unknown)
+			.count();
+		(nb_unknown, self.groups.iter().sum())
+	}
+
 	/// Counts possible arrangements of unknown values to match groups.
 	#[must_use]
 	pub fn get_arrangements_count(&self) -> usize {
-		// Brute force for now, use brain later.
+		self.count_arrangements_from(0, 0, 0, &mut HashMap::new())
+	}
+
+	/// Counts arrangements of the points from `point_index` onwards,
+	/// given `group_index` groups are already complete and the current run of broken points is `run_len` long.
+	fn count_arrangements_from(
+		&self,
+		point_index: usize,
+		group_index: usize,
+		run_len: usize,
+		memo: &mut ArrangementsMemo,
+	) -> usize {
+		let key = (point_index, group_index, run_len);
+		if let Some(&count) = memo.get(&key) {
+			return count;
+		}
+
+		let next_group = self.groups.get(group_index).copied();
+		let count = if let Some(&point) = self.points.get(point_index) {
+			let mut count = 0;
+			if matches!(point, PointState::Working | PointState::Unknown) {
+				if run_len == 0 {
+					count += self.count_arrangements_from(point_index + 1, group_index, 0, memo);
+				} else if next_group == Some(run_len) {
+					count +=
+						self.count_arrangements_from(point_index + 1, group_index + 1, 0, memo);
+				}
+			}
+			if matches!(
+				point,
+				PointState::Broken | PointState::ForcedBroken | PointState::Unknown
+			) && next_group.is_some_and(|group_len| run_len < group_len)
+			{
+				count +=
+					self.count_arrangements_from(point_index + 1, group_index, run_len + 1, memo);
+			}
+			count
+		} else {
+			let is_done = if run_len == 0 {
+				group_index == self.groups.len()
+			} else {
+				group_index + 1 == self.groups.len() && next_group == Some(run_len)
+			};
+			is_done.into()
+		};
+
+		memo.insert(key, count);
+		count
+	}
+
+	/// Counts possible arrangements by trying every combination of unknown values.
+	/// Exponential, so only suitable as a reference on short rows.
+	#[cfg(test)]
+	#[must_use]
+	pub fn get_arrangements_count_brute_force(&self) -> usize {
+		self.arrangements().len()
+	}
+
+	/// Lists every filled-in row matching the groups, by trying every combination of unknown values.
+	/// Exponential, so only suitable for short rows.
+	#[cfg(test)]
+	#[must_use]
+	pub fn arrangements(&self) -> Vec<Vec<PointState>> {
+		self.fill_unknowns(true)
+	}
+
+	/// Recursively fills in unknown points, keeping the rows that match the groups.
+	/// If `prune`, gives up early on rows without enough room left for the groups.
+	#[cfg(test)]
+	#[must_use]
+	fn fill_unknowns(&self, prune: bool) -> Vec<Vec<PointState>> {
+		if prune && !self.has_room_for_groups() {
+			return vec![];
+		}
 		let first_unknown = self
 			.points
 			.iter()
@@ -38,16 +147,31 @@ impl PointRow {
 		if let Some(index) = first_unknown {
 			let mut fixed = self.clone();
 			fixed.points[index] = PointState::Working;
-			let count_working = fixed.get_arrangements_count();
+			let mut arrangements = fixed.fill_unknowns(prune);
 			fixed.points[index] = PointState::Broken;
-			let count_broken = fixed.get_arrangements_count();
-			count_working + count_broken
+			arrangements.extend(fixed.fill_unknowns(prune));
+			arrangements
+		} else if self.check_groups() {
+			vec![self.points.clone()]
 		} else {
-			self.check_groups().into()
+			vec![]
 		}
 	}
 
+	/// Checks there are at least as many points that could be broken as the groups need.
+	#[cfg(test)]
+	#[must_use]
+	fn has_room_for_groups(&self) -> bool {
+		let nb_maybe_broken = self
+			.points
+			.iter()
+			.filter(|&&point| point != PointState::Working)
+			.count();
+		nb_maybe_broken >= self.groups.iter().sum()
+	}
+
 	/// Once all unknowns are filled in, check whether the resulting groups match the spec.
+	#[cfg(test)]
 	#[must_use]
 	fn check_groups(&self) -> bool {
 		let mut groups: Vec<usize> = vec![];
@@ -60,7 +184,7 @@ impl PointRow {
 					}
 					cur_group_len = 0;
 				}
-				PointState::Broken => {
+				PointState::Broken | PointState::ForcedBroken => {
 					cur_group_len += 1;
 				}
 				PointState::Unknown => panic!("Only call this on already filled-in rows"),
@@ -73,13 +197,31 @@ impl PointRow {
 	}
 }
 
+/// Counts arrangements of a row given in puzzle notation, e.g. `"???.###"` with groups `[1, 1, 3]`.
+/// # Panics
+/// If the pattern has characters other than `.#?!`.
+#[cfg(test)]
+#[must_use]
+pub fn count_arrangements_str(pattern: &str, groups: &[usize]) -> usize {
+	let points = pattern
+		.chars()
+		.map(|ch| PointState::from_char(ch).expect("Invalid point"))
+		.collect();
+	let row = PointRow {
+		points,
+		groups: groups.to_vec(),
+	};
+	row.get_arrangements_count()
+}
+
 #[cfg(test)]
 mod test {
 
 	use super::{
-		PointState::{Broken, Unknown, Working},
+		PointState::{Broken, ForcedBroken, Unknown, Working},
 		*,
 	};
+	use crate::parse_input::parse_full;
 
 	#[test]
 	fn test_minimal() {
@@ -126,6 +268,120 @@ mod test {
 		assert_eq!(row.get_arrangements_count(), 2);
 	}
 
+	#[test]
+	fn test_no_groups() {
+		let row = PointRow {
+			points: vec![Unknown, Unknown, Unknown],
+			groups: vec![],
+		};
+		assert_eq!(row.get_arrangements_count(), 1);
+		assert_eq!(row.arrangements(), vec![vec![Working, Working, Working]]);
+	}
+
+	#[test]
+	fn test_no_groups_forced_broken() {
+		let row = PointRow {
+			points: vec![Broken, Unknown, Unknown],
+			groups: vec![],
+		};
+		assert_eq!(row.get_arrangements_count(), 0);
+		assert_eq!(row.get_arrangements_count_brute_force(), 0);
+	}
+
+	#[test]
+	fn test_all_working() {
+		let row = PointRow {
+			points: vec![Working, Working],
+			groups: vec![],
+		};
+		assert_eq!(row.get_arrangements_count(), 1);
+		let row = PointRow {
+			points: vec![Working, Working],
+			groups: vec![1],
+		};
+		assert_eq!(row.get_arrangements_count(), 0);
+	}
+
+	#[test]
+	fn test_forced_broken() {
+		let row = PointRow {
+			points: vec![ForcedBroken, Unknown, Unknown],
+			groups: vec![2],
+		};
+		assert_eq!(row.get_arrangements_count(), 1);
+		assert_eq!(
+			row.arrangements(),
+			vec![vec![ForcedBroken, Broken, Working]]
+		);
+		assert_eq!(count_arrangements_str("!??", &[2]), 1);
+	}
+
+	#[test]
+	fn test_complexity() {
+		// ?###???????? 3,2,1
+		let row = PointRow {
+			points: vec![
+				Unknown, Broken, Broken, Broken, Unknown, Unknown, Unknown, Unknown, Unknown,
+				Unknown, Unknown, Unknown,
+			],
+			groups: vec![3, 2, 1],
+		};
+		assert_eq!(row.complexity(), (9, 6));
+	}
+
+	#[test]
+	fn test_arrangements() {
+		let row = PointRow {
+			points: vec![Unknown, Working, Unknown],
+			groups: vec![1],
+		};
+		let expected = vec![
+			vec![Working, Working, Broken],
+			vec![Broken, Working, Working],
+		];
+		assert_eq!(row.arrangements(), expected);
+	}
+
+	#[test]
+	fn test_arrangements_impossible() {
+		let row = PointRow {
+			points: vec![Broken, Unknown, Broken],
+			groups: vec![3, 1],
+		};
+		assert_eq!(row.arrangements(), Vec::<Vec<PointState>>::new());
+	}
+
+	#[test]
+	fn test_pruned_sample() {
+		let rows = parse_full(include_str!("../input_sample.txt"));
+		for row in rows {
+			assert_eq!(row.fill_unknowns(true), row.fill_unknowns(false));
+		}
+	}
+
+	#[test]
+	fn test_pruned_no_room() {
+		let row = PointRow {
+			points: vec![Unknown, Working, Unknown],
+			groups: vec![1, 2],
+		};
+		assert_eq!(row.fill_unknowns(true), Vec::<Vec<PointState>>::new());
+		assert_eq!(row.fill_unknowns(false), Vec::<Vec<PointState>>::new());
+	}
+
+	#[test]
+	fn test_count_arrangements_str_sample() {
+		let counts = [
+			count_arrangements_str("???.###", &[1, 1, 3]),
+			count_arrangements_str(".??..??...?##.", &[1, 1, 3]),
+			count_arrangements_str("?#?#?#?#?#?#?#?", &[1, 3, 1, 6]),
+			count_arrangements_str("????.#...#...", &[4, 1, 1]),
+			count_arrangements_str("????.######..#####.", &[1, 6, 5]),
+			count_arrangements_str("?###????????", &[3, 2, 1]),
+		];
+		assert_eq!(counts, [1, 4, 1, 1, 4, 10]);
+	}
+
 	#[test]
 	fn test_unfold() {
 		let folded = PointRow {
@@ -140,4 +396,47 @@ mod test {
 		};
 		assert_eq!(folded.unfold(), expected);
 	}
+
+	#[test]
+	fn test_unfold_separators() {
+		let folded = PointRow {
+			points: vec![Broken, Working, Broken],
+			groups: vec![1, 1],
+		};
+		// #.#?#.#?#.#?#.#?#.#
+		let expected = PointRow {
+			points: vec![
+				Broken, Working, Broken, Unknown, Broken, Working, Broken, Unknown, Broken,
+				Working, Broken, Unknown, Broken, Working, Broken, Unknown, Broken, Working,
+				Broken,
+			],
+			groups: [1, 1].repeat(5),
+		};
+		assert_eq!(folded.unfold(), expected);
+	}
+
+	#[test]
+	fn test_unfold_by_1() {
+		let folded = PointRow {
+			points: vec![Broken, Unknown, Working],
+			groups: vec![1],
+		};
+		assert_eq!(folded.unfold_by(1), folded);
+	}
+
+	#[test]
+	fn test_unfold_by_2() {
+		let folded = PointRow {
+			points: vec![Unknown],
+			groups: vec![1],
+		};
+		let expected = PointRow {
+			points: vec![Unknown, Unknown, Unknown],
+			groups: vec![1, 1],
+		};
+		let unfolded = folded.unfold_by(2);
+		assert_eq!(unfolded, expected);
+		// only #.# fits
+		assert_eq!(unfolded.get_arrangements_count(), 1);
+	}
 }
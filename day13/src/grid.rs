@@ -1,23 +1,107 @@
-/// A 2D bool array.
+use std::{fmt, iter};
+
+/// A 2D array of tiles that can be compared for reflections.
 #[derive(Debug, Clone, Eq, Hash, Ord, PartialEq, PartialOrd)]
-pub struct Grid {
+pub struct Grid<T> {
 	pub nb_rows: usize,
 	pub nb_cols: usize,
-	pub tiles: Vec<Vec<bool>>,
+	pub tiles: Vec<Vec<T>>,
 }
 
-/// Tests whether the vector is reflected just before the given index, ignoring extra items on either side.
+/// A grid of ash (`false`) and rocks (`true`).
+pub type BoolGrid = Grid<bool>;
+
+impl fmt::Display for BoolGrid {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		for row in &self.tiles {
+			for &tile in row {
+				write!(f, "{}", if tile { '#' } else { '.' })?;
+			}
+			writeln!(f)?;
+		}
+		Ok(())
+	}
+}
+
+#[cfg(test)]
+mod test_display {
+	use super::*;
+	use crate::parse_input::parse_full;
+
+	#[test]
+	fn test_display() {
+		let input = Grid {
+			nb_rows: 2,
+			nb_cols: 3,
+			tiles: vec![vec![true, false, false], vec![false, true, true]],
+		};
+		assert_eq!(input.to_string(), "#..\n.##\n");
+	}
+
+	#[test]
+	fn test_round_trip_sample() {
+		let grids = parse_full(include_str!("../input_sample.txt"));
+		for grid in grids {
+			assert_eq!(parse_full(&grid.to_string()), vec![grid]);
+		}
+	}
+}
+
+/// Swaps rows and columns.
 #[must_use]
-fn is_reflected_at(row: &[bool], mirror_index: usize) -> bool {
-	let max_distance = mirror_index.min(row.len() - mirror_index);
-	for distance in 0..max_distance {
-		let before = row[mirror_index - distance - 1];
-		let after = row[mirror_index + distance];
-		if before != after {
-			return false;
+pub fn transpose<T: Clone>(grid: &Grid<T>) -> Grid<T> {
+	Grid {
+		nb_rows: grid.nb_cols,
+		nb_cols: grid.nb_rows,
+		tiles: common::transpose(&grid.tiles),
+	}
+}
+
+#[cfg(test)]
+mod test_transpose {
+	use super::*;
+	use crate::parse_input::parse_full;
+
+	#[test]
+	fn test_transpose() {
+		let input = Grid {
+			nb_rows: 2,
+			nb_cols: 3,
+			tiles: vec![vec![true, true, false], vec![false, true, false]],
+		};
+		let expected = Grid {
+			nb_rows: 3,
+			nb_cols: 2,
+			tiles: vec![vec![true, false], vec![true, true], vec![false, false]],
+		};
+		assert_eq!(transpose(&input), expected);
+		assert_eq!(transpose(&transpose(&input)), input);
+	}
+
+	#[test]
+	fn test_sample_reflections() {
+		let grids = parse_full(include_str!("../input_sample.txt"));
+		let reflects: Vec<Vec<usize>> = grids.iter().map(get_reflects).collect();
+		assert_eq!(reflects, vec![vec![5], vec![400]]);
+		for grid in &grids {
+			assert_eq!(transpose(&transpose(grid)), *grid);
 		}
 	}
-	true
+}
+
+/// Tests whether the vector is reflected just before the given index, ignoring extra items on either side.
+#[must_use]
+pub fn is_reflected_at<T: PartialEq>(row: &[T], mirror_index: usize) -> bool {
+	mismatch_count_at(row, mirror_index) == 0
+}
+
+/// Counts positions that differ from their mirror image across the given index, ignoring extra items on either side.
+#[must_use]
+pub fn mismatch_count_at<T: PartialEq>(row: &[T], mirror_index: usize) -> usize {
+	let max_distance = mirror_index.min(row.len() - mirror_index);
+	(0..max_distance)
+		.filter(|distance| row[mirror_index - distance - 1] != row[mirror_index + distance])
+		.count()
 }
 
 #[cfg(test)]
@@ -89,9 +173,78 @@ mod test_is_reflected_at {
 	}
 }
 
+#[cfg(test)]
+mod test_mismatch_count_at {
+	use super::*;
+
+	#[test]
+	fn test_left_edge_match() {
+		let input = vec![false, false, true, true, true];
+		assert_eq!(mismatch_count_at(&input, 1), 0);
+	}
+
+	#[test]
+	fn test_left_edge_mismatch() {
+		let input = vec![false, true, true, true, true];
+		assert_eq!(mismatch_count_at(&input, 1), 1);
+	}
+
+	#[test]
+	fn test_right_edge_match() {
+		let input = vec![false, false, true, true, true];
+		assert_eq!(mismatch_count_at(&input, 4), 0);
+	}
+
+	#[test]
+	fn test_lean_left_match() {
+		let input = vec![
+			false, false, true, true, false, false, true, true, false, false, true, false,
+		];
+		assert_eq!(mismatch_count_at(&input, 5), 0);
+	}
+
+	#[test]
+	fn test_lean_left_mismatch() {
+		let input = vec![
+			false, false, true, true, false, false, true, true, true, false, true, false,
+		];
+		assert_eq!(mismatch_count_at(&input, 5), 1);
+	}
+
+	#[test]
+	fn test_lean_right_match() {
+		let input = vec![false, false, false, false, false, true, true, false];
+		assert_eq!(mismatch_count_at(&input, 6), 0);
+	}
+
+	#[test]
+	fn test_lean_right_mismatch() {
+		let input = vec![false, false, false, false, true, true, true, false];
+		assert_eq!(mismatch_count_at(&input, 6), 1);
+	}
+
+	#[test]
+	fn test_middle_match() {
+		let input = vec![false, true, false, false, true, false];
+		assert_eq!(mismatch_count_at(&input, 3), 0);
+	}
+
+	#[test]
+	fn test_middle_mismatch() {
+		let input = vec![true, true, false, false, true, false];
+		assert_eq!(mismatch_count_at(&input, 3), 1);
+	}
+
+	#[test]
+	fn test_several_mismatches() {
+		let input = vec![true, true, false, false];
+		assert_eq!(mismatch_count_at(&input, 2), 2);
+	}
+}
+
 /// Gets all indices forming a vertical reflection in the grid.
 #[must_use]
-pub fn get_reflects_vert(grid: &Grid) -> Vec<usize> {
+pub fn get_reflects_vert<T: PartialEq>(grid: &Grid<T>) -> Vec<usize> {
 	let mut reflect_indices: Vec<usize> = vec![];
 	for mirror_index in 1..grid.nb_cols {
 		if grid
@@ -162,17 +315,8 @@ mod test_reflect_vert {
 
 /// Gets all indices forming a horizontal reflection in the grid.
 #[must_use]
-pub fn get_reflects_horiz(grid: &Grid) -> Vec<usize> {
-	let cols: Vec<Vec<bool>> = (0..grid.nb_cols)
-		.map(|col_index| grid.tiles.iter().map(|row| row[col_index]).collect())
-		.collect();
-	let mut reflect_indices: Vec<usize> = vec![];
-	for mirror_index in 1..grid.nb_rows {
-		if cols.iter().all(|col| is_reflected_at(col, mirror_index)) {
-			reflect_indices.push(mirror_index);
-		}
-	}
-	reflect_indices
+pub fn get_reflects_horiz<T: Clone + PartialEq>(grid: &Grid<T>) -> Vec<usize> {
+	get_reflects_vert(&transpose(grid))
 }
 
 #[cfg(test)]
@@ -231,44 +375,253 @@ mod test_reflect_horiz {
 	}
 }
 
-/// Gets all indices forming a reflection in the grid (×100 if horizontal).
+/// Orientation of a mirror line.
+#[derive(Debug, Clone, Copy, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub enum Axis {
+	Horizontal,
+	Vertical,
+}
+
+impl Axis {
+	/// Weights a reflection index for the puzzle answer (×100 if horizontal).
+	#[must_use]
+	pub fn score(self, index: usize) -> usize {
+		match self {
+			Axis::Horizontal => 100 * index,
+			Axis::Vertical => index,
+		}
+	}
+}
+
+/// Gets all reflections in the grid, horizontal first, with their orientation and raw index.
 #[must_use]
-pub fn get_reflects(grid: &Grid) -> Vec<usize> {
+pub fn reflects_detailed<T: Clone + PartialEq>(grid: &Grid<T>) -> Vec<(Axis, usize)> {
 	let indices_vert = get_reflects_vert(grid);
 	let indices_horiz = get_reflects_horiz(grid);
 
 	indices_horiz
 		.into_iter()
-		.map(|index| 100 * index)
-		.chain(indices_vert)
+		.map(|index| (Axis::Horizontal, index))
+		.chain(
+			indices_vert
+				.into_iter()
+				.map(|index| (Axis::Vertical, index)),
+		)
 		.collect()
 }
 
-/// Gets the unique reflection index of a grid (×100 if horizontal).
+/// Gets all indices forming a reflection in the grid (×100 if horizontal).
 #[must_use]
-pub fn get_reflect(grid: &Grid) -> usize {
+pub fn get_reflects<T: Clone + PartialEq>(grid: &Grid<T>) -> Vec<usize> {
+	reflects_detailed(grid)
+		.into_iter()
+		.map(|(axis, index)| axis.score(index))
+		.collect()
+}
+
+#[cfg(test)]
+mod test_reflects_detailed {
+	use super::*;
+
+	#[test]
+	fn test_vertical() {
+		let input = Grid {
+			nb_rows: 2,
+			nb_cols: 3,
+			tiles: vec![vec![true, true, false], vec![true; 3]],
+		};
+		assert_eq!(reflects_detailed(&input), vec![(Axis::Vertical, 1)]);
+		assert_eq!(get_reflects(&input), vec![1]);
+	}
+
+	#[test]
+	fn test_both() {
+		let input = Grid {
+			nb_rows: 2,
+			nb_cols: 3,
+			tiles: vec![vec![true, true, false], vec![true, true, false]],
+		};
+		assert_eq!(
+			reflects_detailed(&input),
+			vec![(Axis::Horizontal, 1), (Axis::Vertical, 1)]
+		);
+		assert_eq!(get_reflects(&input), vec![100, 1]);
+	}
+}
+
+/// Counts horizontal and vertical reflections across all grids.
+#[cfg(test)]
+#[must_use]
+pub fn reflection_histogram<T: Clone + PartialEq>(grids: &[Grid<T>]) -> (usize, usize) {
+	grids
+		.iter()
+		.flat_map(reflects_detailed)
+		.fold((0, 0), |(nb_horiz, nb_vert), (axis, _)| match axis {
+			Axis::Horizontal => (nb_horiz + 1, nb_vert),
+			Axis::Vertical => (nb_horiz, nb_vert + 1),
+		})
+}
+
+#[cfg(test)]
+mod test_reflection_histogram {
+	use super::*;
+	use crate::parse_input::parse_full;
+
+	#[test]
+	fn test_sample() {
+		let grids = parse_full(include_str!("../input_sample.txt"));
+		assert_eq!(reflection_histogram(&grids), (1, 1));
+	}
+
+	#[test]
+	fn test_empty() {
+		assert_eq!(reflection_histogram::<bool>(&[]), (0, 0));
+	}
+}
+
+/// Reasons a grid doesn't have a unique reflection.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum ReflectError {
+	NoReflection,
+	/// All the reflection indices found (×100 if horizontal).
+	MultipleReflections(Vec<usize>),
+}
+
+/// Gets the unique reflection index of a grid (×100 if horizontal).
+/// # Errors
+/// If the grid has no reflection or more than one.
+pub fn try_get_reflect<T: Clone + PartialEq>(grid: &Grid<T>) -> Result<usize, ReflectError> {
 	let reflects = get_reflects(grid);
-	assert_eq!(reflects.len(), 1, "Grid should have unique reflection");
-	reflects[0]
+	match reflects.len() {
+		0 => Err(ReflectError::NoReflection),
+		1 => Ok(reflects[0]),
+		_ => Err(ReflectError::MultipleReflections(reflects)),
+	}
+}
+
+/// Gets the unique reflection index of a grid (×100 if horizontal).
+/// # Panics
+/// If the grid has no reflection or more than one.
+#[must_use]
+pub fn get_reflect<T: Clone + PartialEq>(grid: &Grid<T>) -> usize {
+	try_get_reflect(grid).expect("Grid should have unique reflection")
+}
+
+#[cfg(test)]
+mod test_generic_tiles {
+	use super::*;
+
+	#[test]
+	fn test_char_grid() {
+		// abba
+		// cddc
+		// cddc
+		let input = Grid {
+			nb_rows: 3,
+			nb_cols: 4,
+			tiles: vec![
+				vec!['a', 'b', 'b', 'a'],
+				vec!['c', 'd', 'd', 'c'],
+				vec!['c', 'd', 'd', 'c'],
+			],
+		};
+		assert_eq!(
+			reflects_detailed(&input),
+			vec![(Axis::Horizontal, 2), (Axis::Vertical, 2)]
+		);
+	}
+
+	#[test]
+	fn test_int_grid_smudge() {
+		let input = Grid {
+			nb_rows: 2,
+			nb_cols: 3,
+			tiles: vec![vec![1, 2, 3], vec![1, 2, 4]],
+		};
+		assert_eq!(get_reflects(&input), vec![]);
+		assert_eq!(reflect_and_smudge_pos(&input), Some((100, (0, 2))));
+	}
+}
+
+#[cfg(test)]
+mod test_try_get_reflect {
+	use super::*;
+
+	#[test]
+	fn test_unique() {
+		let input = Grid {
+			nb_rows: 2,
+			nb_cols: 3,
+			tiles: vec![vec![true, true, false], vec![true; 3]],
+		};
+		assert_eq!(try_get_reflect(&input), Ok(1));
+	}
+
+	#[test]
+	fn test_none() {
+		let input = Grid {
+			nb_rows: 1,
+			nb_cols: 2,
+			tiles: vec![vec![false, true]],
+		};
+		assert_eq!(try_get_reflect(&input), Err(ReflectError::NoReflection));
+	}
+
+	#[test]
+	fn test_symmetric() {
+		// #..#
+		// .##.
+		// .##.
+		// #..#
+		let input = Grid {
+			nb_rows: 4,
+			nb_cols: 4,
+			tiles: vec![
+				vec![true, false, false, true],
+				vec![false, true, true, false],
+				vec![false, true, true, false],
+				vec![true, false, false, true],
+			],
+		};
+		assert_eq!(
+			try_get_reflect(&input),
+			Err(ReflectError::MultipleReflections(vec![200, 2]))
+		);
+	}
 }
 
 /// Clones the grid and flips one tile in the clone.
 #[must_use]
-fn flip_at(grid: &Grid, row_index: usize, col_index: usize) -> Grid {
+fn flip_at(grid: &BoolGrid, row_index: usize, col_index: usize) -> BoolGrid {
 	let mut new_grid = grid.clone();
 	new_grid.tiles[row_index][col_index] = !new_grid.tiles[row_index][col_index];
 	new_grid
 }
 
-/// Iterates all variations of the grid with one tile flipped.
-fn flip_one(grid: &Grid) -> impl Iterator<Item = Grid> + '_ {
-	(0..grid.nb_rows).flat_map(move |row_index| {
-		(0..grid.nb_cols).map(move |col_index| flip_at(grid, row_index, col_index))
-	})
+/// Iterates over all variations of the grid with `nb_flips` distinct tiles flipped.
+fn flip_n(grid: &BoolGrid, nb_flips: usize) -> impl Iterator<Item = BoolGrid> {
+	flip_n_from(grid.clone(), nb_flips, 0)
+}
+
+/// Iterates over all variations of the grid with `nb_flips` distinct tiles flipped, only flipping tiles from `first_tile` onwards in reading order.
+fn flip_n_from(
+	grid: BoolGrid,
+	nb_flips: usize,
+	first_tile: usize,
+) -> Box<dyn Iterator<Item = BoolGrid>> {
+	if nb_flips == 0 {
+		return Box::new(iter::once(grid));
+	}
+	Box::new(
+		(first_tile..grid.nb_rows * grid.nb_cols).flat_map(move |tile| {
+			let flipped = flip_at(&grid, tile / grid.nb_cols, tile % grid.nb_cols);
+			flip_n_from(flipped, nb_flips - 1, tile + 1)
+		}),
+	)
 }
 
 #[cfg(test)]
-mod test_flip_one {
+mod test_flip_n {
 	use super::*;
 
 	#[test]
@@ -278,8 +631,7 @@ mod test_flip_one {
 			nb_cols: 0,
 			tiles: vec![],
 		};
-		let flipped: Vec<Grid> = flip_one(&input).collect();
-		assert!(flipped.is_empty());
+		assert_eq!(flip_n(&input, 1).next(), None);
 	}
 
 	#[test]
@@ -289,7 +641,7 @@ mod test_flip_one {
 			nb_cols: 2,
 			tiles: vec![vec![true, false], vec![false, true]],
 		};
-		let flipped: Vec<Grid> = flip_one(&input).collect();
+		let flipped: Vec<BoolGrid> = flip_n(&input, 1).collect();
 		let expected = vec![
 			Grid {
 				nb_rows: 2,
@@ -314,11 +666,52 @@ mod test_flip_one {
 		];
 		assert_eq!(flipped, expected);
 	}
+
+	#[test]
+	fn test_no_flips() {
+		let input = Grid {
+			nb_rows: 1,
+			nb_cols: 2,
+			tiles: vec![vec![true, false]],
+		};
+		assert_eq!(flip_n(&input, 0).collect::<Vec<_>>(), vec![input]);
+	}
+
+	#[test]
+	fn test_two_flips() {
+		let input = Grid {
+			nb_rows: 1,
+			nb_cols: 3,
+			tiles: vec![vec![true, false, false]],
+		};
+		let flipped: Vec<BoolGrid> = flip_n(&input, 2).collect();
+		let expected = vec![
+			Grid {
+				nb_rows: 1,
+				nb_cols: 3,
+				tiles: vec![vec![false, true, false]],
+			},
+			Grid {
+				nb_rows: 1,
+				nb_cols: 3,
+				tiles: vec![vec![false, false, true]],
+			},
+			Grid {
+				nb_rows: 1,
+				nb_cols: 3,
+				tiles: vec![vec![true, true, true]],
+			},
+		];
+		assert_eq!(flipped, expected);
+	}
 }
 
 /// Gets a new reflection index of a grid (×100 if horizontal, None if none exists), excluding the old index.
 #[must_use]
-pub fn get_new_reflect(new_grid: &Grid, old_reflect: usize) -> Option<usize> {
+pub fn get_new_reflect<T: Clone + PartialEq>(
+	new_grid: &Grid<T>,
+	old_reflect: usize,
+) -> Option<usize> {
 	let mut reflects = get_reflects(new_grid);
 	// The flip must make a difference
 	reflects.retain(|&new_reflect| new_reflect != old_reflect);
@@ -330,15 +723,153 @@ pub fn get_new_reflect(new_grid: &Grid, old_reflect: usize) -> Option<usize> {
 	}
 }
 
-/// Tries all flips on a grid, finds the one with a unique reflection, and returns its index.
+/// Finds the new reflection index of a grid after a single flip (None if no flip works).
+#[must_use]
+pub fn get_reflect_with_flip(grid: &BoolGrid) -> Option<usize> {
+	get_reflect_with_n_flips(grid, 1)
+}
+
+/// Finds a new reflection index after flipping exactly `nb_flips` tiles on a grid (None if no flips work).
+/// With no flips, this is the grid's own reflection.
+/// A single flip is found by counting mismatches; more flips try all ways of flipping, and take the first with a new reflection.
+/// Grids without a unique reflection to start from give None.
+#[must_use]
+pub fn get_reflect_with_n_flips(grid: &BoolGrid, nb_flips: usize) -> Option<usize> {
+	let old_reflect = try_get_reflect(grid).ok()?;
+	match nb_flips {
+		0 => Some(old_reflect),
+		1 => reflect_with_smudge(grid),
+		_ => flip_n(grid, nb_flips).find_map(|flipped| get_new_reflect(&flipped, old_reflect)),
+	}
+}
+
+#[cfg(test)]
+mod test_get_reflect_with_flip {
+	use super::*;
+
+	#[test]
+	fn test_no_acceptable_flip() {
+		let input = Grid {
+			nb_rows: 1,
+			nb_cols: 2,
+			tiles: vec![vec![true, true]],
+		};
+		assert_eq!(get_reflect(&input), 1);
+		assert_eq!(get_reflect_with_flip(&input), None);
+	}
+
+	#[test]
+	fn test_no_reflection() {
+		// #.
+		// ..
+		let input = Grid {
+			nb_rows: 2,
+			nb_cols: 2,
+			tiles: vec![vec![true, false], vec![false, false]],
+		};
+		assert_eq!(try_get_reflect(&input), Err(ReflectError::NoReflection));
+		assert_eq!(get_reflect_with_n_flips(&input, 0), None);
+		assert_eq!(get_reflect_with_flip(&input), None);
+	}
+}
+
+/// Finds the reflection index of a grid (×100 if horizontal) that would be exact after fixing a single smudged tile
+/// (None if no reflection is off by exactly one tile).
+#[must_use]
+pub fn reflect_with_smudge<T: Clone + PartialEq>(grid: &Grid<T>) -> Option<usize> {
+	reflect_and_smudge_pos(grid).map(|(index, _)| index)
+}
+
+/// Finds the reflection index of a grid (×100 if horizontal) that would be exact after fixing a single smudged tile,
+/// and the row and column of that tile (None if no reflection is off by exactly one tile).
+/// Of the two mismatched tiles, gives the one nearer the top left.
+#[must_use]
+pub fn reflect_and_smudge_pos<T: Clone + PartialEq>(
+	grid: &Grid<T>,
+) -> Option<(usize, (usize, usize))> {
+	if let Some((index, (col, row))) = find_smudge(&transpose(grid).tiles, grid.nb_rows) {
+		return Some((100 * index, (row, col)));
+	}
+	find_smudge(&grid.tiles, grid.nb_cols)
+}
+
+/// Finds the first mirror index where exactly one position across all lines differs from its mirror image.
+/// Returns the index, and the line and position of the mismatch before the mirror.
 #[must_use]
-pub fn get_reflect_with_flip(grid: &Grid) -> usize {
-	let old_reflect = get_reflect(grid);
-	for flipped in flip_one(grid) {
-		let reflect = get_new_reflect(&flipped, old_reflect);
-		if let Some(index) = reflect {
-			return index;
+fn find_smudge<T: PartialEq>(lines: &[Vec<T>], line_len: usize) -> Option<(usize, (usize, usize))> {
+	(1..line_len).find_map(|mirror_index| {
+		let max_distance = mirror_index.min(line_len - mirror_index);
+		let mut mismatches = lines.iter().enumerate().flat_map(|(line_index, line)| {
+			(0..max_distance)
+				.map(move |distance| mirror_index - distance - 1)
+				.filter(|&pos| line[pos] != line[2 * mirror_index - pos - 1])
+				.map(move |pos| (line_index, pos))
+		});
+		match (mismatches.next(), mismatches.next()) {
+			(Some(smudge), None) => Some((mirror_index, smudge)),
+			_ => None,
+		}
+	})
+}
+
+#[cfg(test)]
+mod test_reflect_with_smudge {
+	use super::*;
+	use crate::parse_input::parse_full;
+
+	#[test]
+	fn test_sample() {
+		let grids = parse_full(include_str!("../input_sample.txt"));
+		let smudged: Vec<Option<usize>> = grids.iter().map(reflect_with_smudge).collect();
+		assert_eq!(smudged, vec![Some(300), Some(100)]);
+		for grid in grids {
+			let old_reflect = get_reflect(&grid);
+			let by_flipping =
+				flip_n(&grid, 1).find_map(|flipped| get_new_reflect(&flipped, old_reflect));
+			assert_eq!(reflect_with_smudge(&grid), by_flipping);
 		}
 	}
-	panic!("Grid has no acceptable flip!");
+
+	#[test]
+	fn test_vertical() {
+		let input = Grid {
+			nb_rows: 2,
+			nb_cols: 5,
+			tiles: vec![
+				vec![false, true, false, false, true],
+				vec![false, true, true, true, false],
+			],
+		};
+		assert_eq!(reflect_with_smudge(&input), Some(3));
+	}
+
+	#[test]
+	fn test_smudge_pos_sample() {
+		let grids = parse_full(include_str!("../input_sample.txt"));
+		assert_eq!(reflect_and_smudge_pos(&grids[0]), Some((300, (0, 0))));
+		assert_eq!(reflect_and_smudge_pos(&grids[1]), Some((100, (0, 4))));
+	}
+
+	#[test]
+	fn test_smudge_pos_vertical() {
+		let input = Grid {
+			nb_rows: 2,
+			nb_cols: 5,
+			tiles: vec![
+				vec![false, true, false, false, true],
+				vec![false, true, true, true, false],
+			],
+		};
+		assert_eq!(reflect_and_smudge_pos(&input), Some((3, (1, 1))));
+	}
+
+	#[test]
+	fn test_no_smudge() {
+		let input = Grid {
+			nb_rows: 1,
+			nb_cols: 2,
+			tiles: vec![vec![true, true]],
+		};
+		assert_eq!(reflect_and_smudge_pos(&input), None);
+	}
 }
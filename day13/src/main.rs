@@ -1,5 +1,6 @@
-use std::io::{self, Read};
+use std::env;
 
+use common::{emit, has_flag, read_input, timed};
 use grid::{get_reflect, get_reflect_with_flip};
 use parse_input::parse_full;
 
@@ -14,18 +15,20 @@ fn get_index_sum(input: &str) -> usize {
 }
 
 /// Part 2: sums the reflection indices of all grids with 1 flip, weighted by horizontal vs vertical.
+/// Grids where no flip gives a new reflection are skipped.
 #[must_use]
 fn get_flipped_sum(input: &str) -> usize {
 	let grids = parse_full(input);
 	grids
 		.into_iter()
-		.map(|grid| get_reflect_with_flip(&grid))
+		.filter_map(|grid| get_reflect_with_flip(&grid))
 		.sum()
 }
 
 #[cfg(test)]
 mod test {
 	use super::*;
+	use crate::grid::get_reflect_with_n_flips;
 	const SAMPLE_INPUT: &str = include_str!("../input_sample.txt");
 
 	#[test]
@@ -33,14 +36,40 @@ mod test {
 		assert_eq!(get_index_sum(SAMPLE_INPUT), 405);
 		assert_eq!(get_flipped_sum(SAMPLE_INPUT), 400);
 	}
+
+	#[test]
+	fn test_sample_n_flips() {
+		let grids = parse_full(SAMPLE_INPUT);
+		let sum_with_flips = |nb_flips| -> usize {
+			grids
+				.iter()
+				.filter_map(|grid| get_reflect_with_n_flips(grid, nb_flips))
+				.sum()
+		};
+		assert_eq!(sum_with_flips(0), 405);
+		assert_eq!(sum_with_flips(1), 400);
+	}
+
+	#[test]
+	fn test_skip_no_flip() {
+		let input = format!("{SAMPLE_INPUT}\n##\n");
+		assert_eq!(get_flipped_sum(&input), 400);
+	}
+
+	#[test]
+	fn test_skip_no_reflection() {
+		let input = format!("{SAMPLE_INPUT}\n#.\n..\n");
+		assert_eq!(get_flipped_sum(&input), 400);
+	}
 }
 
 fn main() {
-	let mut input = String::new();
-	io::stdin()
-		.read_to_string(&mut input)
-		.expect("Failed to read input");
+	let input = read_input(env::args().skip(1)).expect("Failed to read input");
 
-	println!("Part 1: {}", get_index_sum(&input));
-	println!("Part 2: {}", get_flipped_sum(&input));
+	let json = has_flag("--json");
+	emit(
+		("Part 1", timed("Part 1", || get_index_sum(&input))),
+		("Part 2", timed("Part 2", || get_flipped_sum(&input))),
+		json,
+	);
 }
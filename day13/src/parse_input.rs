@@ -1,35 +1,76 @@
-use crate::grid::Grid;
+use crate::grid::{BoolGrid, Grid};
+
+/// Reasons the input can't be read as grids.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum ParseError {
+	/// A character other than `.` or `#`.
+	InvalidTile(char),
+	/// The grid with this 0-based index has rows of different lengths.
+	RaggedGrid(usize),
+}
 
 /// Reads one tile.
-#[must_use]
-fn read_tile(c: char) -> bool {
+fn read_tile(c: char) -> Result<bool, ParseError> {
 	match c {
-		'.' => false,
-		'#' => true,
-		_ => unreachable!(),
+		'.' => Ok(false),
+		'#' => Ok(true),
+		_ => Err(ParseError::InvalidTile(c)),
 	}
 }
 
-/// Reads a board from a string.
-#[must_use]
-fn parse_grid(s: &str) -> Grid {
-	let tiles: Vec<Vec<bool>> = s
-		.lines()
+/// Reads a board from its non-empty lines.
+fn parse_grid(lines: &[&str], grid_index: usize) -> Result<BoolGrid, ParseError> {
+	let tiles = lines
+		.iter()
 		.map(|line| line.chars().map(read_tile).collect())
-		.collect();
-	Grid {
+		.collect::<Result<Vec<Vec<bool>>, ParseError>>()?;
+	let nb_cols = tiles.first().map_or(0, Vec::len);
+	if tiles.iter().any(|row| row.len() != nb_cols) {
+		return Err(ParseError::RaggedGrid(grid_index));
+	}
+	Ok(Grid {
 		nb_rows: tiles.len(),
-		nb_cols: tiles.first().expect("Empty grid").len(),
+		nb_cols,
 		tiles,
+	})
+}
+
+/// Groups lines into grids, splitting on any run of blank lines.
+fn split_grids(input: &str) -> Vec<Vec<&str>> {
+	let mut grids = vec![];
+	let mut current = vec![];
+	for line in input.lines() {
+		if line.trim().is_empty() {
+			if !current.is_empty() {
+				grids.push(std::mem::take(&mut current));
+			}
+		} else {
+			current.push(line);
+		}
+	}
+	if !current.is_empty() {
+		grids.push(current);
 	}
+	grids
+}
+
+/// Parses the whole input, tolerating Windows line endings and extra blank lines.
+/// # Errors
+/// On an unknown tile, or a grid whose rows aren't all the same length.
+pub fn try_parse_full(input: &str) -> Result<Vec<BoolGrid>, ParseError> {
+	split_grids(input)
+		.iter()
+		.enumerate()
+		.map(|(grid_index, lines)| parse_grid(lines, grid_index))
+		.collect()
 }
 
 /// Parses the whole input.
 /// # Panics
 /// On any parse error.
 #[must_use]
-pub fn parse_full(input: &str) -> Vec<Grid> {
-	input.split("\n\n").map(parse_grid).collect()
+pub fn parse_full(input: &str) -> Vec<BoolGrid> {
+	try_parse_full(input).expect("Parse error")
 }
 
 #[cfg(test)]
@@ -53,4 +94,52 @@ mod test {
 		];
 		assert_eq!(parse_full(input), expected);
 	}
+
+	#[test]
+	fn test_parse_no_final_newline() {
+		let input = "...\n###\n\n.#";
+		let expected = vec![
+			Grid {
+				nb_rows: 2,
+				nb_cols: 3,
+				tiles: vec![vec![false; 3], vec![true; 3]],
+			},
+			Grid {
+				nb_rows: 1,
+				nb_cols: 2,
+				tiles: vec![vec![false, true]],
+			},
+		];
+		assert_eq!(parse_full(input), expected);
+	}
+
+	#[test]
+	fn test_parse_tolerant() {
+		let input = "...\r\n###\r\n\r\n\r\n.#\r\n\r\n\n";
+		let expected = vec![
+			Grid {
+				nb_rows: 2,
+				nb_cols: 3,
+				tiles: vec![vec![false; 3], vec![true; 3]],
+			},
+			Grid {
+				nb_rows: 1,
+				nb_cols: 2,
+				tiles: vec![vec![false, true]],
+			},
+		];
+		assert_eq!(parse_full(input), expected);
+	}
+
+	#[test]
+	fn test_try_parse_ragged() {
+		let input = "...\n###\n\n.#\n#\n";
+		assert_eq!(try_parse_full(input), Err(ParseError::RaggedGrid(1)));
+	}
+
+	#[test]
+	fn test_try_parse_invalid_tile() {
+		let input = "..O\n###\n";
+		assert_eq!(try_parse_full(input), Err(ParseError::InvalidTile('O')));
+	}
 }
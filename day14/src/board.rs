@@ -1,6 +1,9 @@
+use std::collections::hash_map::DefaultHasher;
 use std::fmt;
+use std::hash::{Hash, Hasher};
 
-use crate::pretty::{colourise, step_frame};
+use crate::pretty::step_frame;
+use common::{checked_add, colourise, detect_cycle_within};
 
 /// Tile a rock can occupy.
 #[derive(Debug, Clone, Copy, Eq, Hash, Ord, PartialEq, PartialOrd)]
@@ -21,6 +24,15 @@ impl fmt::Display for Tile {
 	}
 }
 
+/// Direction rocks can slide in.
+#[derive(Debug, Clone, Copy, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub enum Direction {
+	North,
+	South,
+	East,
+	West,
+}
+
 /// A 2D array of rocks.
 #[derive(Debug, Clone, Eq, Hash, Ord, PartialEq, PartialOrd)]
 pub struct Board {
@@ -29,15 +41,6 @@ pub struct Board {
 	pub tiles: Vec<Vec<Tile>>,
 }
 
-/// Signed addition on usize
-/// # Panics
-/// On out-of-bounds.
-#[must_use]
-fn add(a: usize, b: isize) -> usize {
-	let a_signed: isize = a.try_into().expect("Out of bounds: too big");
-	(a_signed + b).try_into().expect("Out of bounds: negative")
-}
-
 impl Board {
 	/// Builds a board from an 2D array of tiles.
 	#[must_use]
@@ -49,20 +52,41 @@ impl Board {
 		}
 	}
 
-	/// Slides all the movable rocks as far as possible in a given direction.
-	pub fn slide_by(
-		&mut self,
-		row_range: Vec<usize>,
-		col_range: Vec<usize>,
-		row_step: isize,
-		col_step: isize,
-		reporting: bool,
-	) {
+	/// Moves each movable rock by at most one tile in a given direction.
+	/// Returns whether any rock moved.
+	pub fn slide_step(&mut self, direction: Direction) -> bool {
+		let (row_range, col_range, row_step, col_step): (Vec<usize>, Vec<usize>, isize, isize) =
+			match direction {
+				Direction::North => (
+					(1..self.nb_rows).collect(),
+					(0..self.nb_cols).collect(),
+					-1,
+					0,
+				),
+				Direction::South => (
+					(0..self.nb_rows - 1).rev().collect(),
+					(0..self.nb_cols).collect(),
+					1,
+					0,
+				),
+				Direction::West => (
+					(0..self.nb_rows).collect(),
+					(1..self.nb_cols).collect(),
+					0,
+					-1,
+				),
+				Direction::East => (
+					(0..self.nb_rows).collect(),
+					(0..self.nb_cols - 1).rev().collect(),
+					0,
+					1,
+				),
+			};
 		let mut any_moved = false;
 		for &row_here in &row_range {
 			for &col_here in &col_range {
-				let row_to = add(row_here, row_step);
-				let col_to = add(col_here, col_step);
+				let row_to = checked_add(row_here, row_step).expect("Out of bounds");
+				let col_to = checked_add(col_here, col_step).expect("Out of bounds");
 				if self.tiles[row_here][col_here] == Tile::Movable
 					&& self.tiles[row_to][col_to] == Tile::Empty
 				{
@@ -72,49 +96,39 @@ impl Board {
 				}
 			}
 		}
-		if reporting {
-			step_frame();
-			println!("{}", &self);
-		}
-		if any_moved {
-			self.slide_by(row_range, col_range, row_step, col_step, reporting);
+		any_moved
+	}
+
+	/// Slides all the movable rocks as far as possible in a given direction.
+	pub fn slide(&mut self, direction: Direction, reporting: bool) {
+		let mut any_moved = true;
+		while any_moved {
+			any_moved = self.slide_step(direction);
+			if reporting {
+				step_frame();
+				println!("{}", &self);
+			}
 		}
 	}
 
 	/// Slides all the movable rocks as far north as possible.
 	pub fn slide_north(&mut self, reporting: bool) {
-		let row_range: Vec<usize> = (1..self.nb_rows).collect();
-		let col_range: Vec<usize> = (0..self.nb_cols).collect();
-		let row_step = -1;
-		let col_step = 0;
-		self.slide_by(row_range, col_range, row_step, col_step, reporting);
+		self.slide(Direction::North, reporting);
 	}
 
 	/// Slides all the movable rocks as far south as possible.
 	pub fn slide_south(&mut self, reporting: bool) {
-		let row_range: Vec<usize> = (0..self.nb_rows - 1).rev().collect();
-		let col_range: Vec<usize> = (0..self.nb_cols).collect();
-		let row_step = 1;
-		let col_step = 0;
-		self.slide_by(row_range, col_range, row_step, col_step, reporting);
+		self.slide(Direction::South, reporting);
 	}
 
 	/// Slides all the movable rocks as far west as possible.
 	pub fn slide_west(&mut self, reporting: bool) {
-		let row_range: Vec<usize> = (0..self.nb_rows).collect();
-		let col_range: Vec<usize> = (1..self.nb_cols).collect();
-		let row_step = 0;
-		let col_step = -1;
-		self.slide_by(row_range, col_range, row_step, col_step, reporting);
+		self.slide(Direction::West, reporting);
 	}
 
 	/// Slides all the movable rocks as far east as possible.
 	pub fn slide_east(&mut self, reporting: bool) {
-		let row_range: Vec<usize> = (0..self.nb_rows).collect();
-		let col_range: Vec<usize> = (0..self.nb_cols - 1).rev().collect();
-		let row_step = 0;
-		let col_step = 1;
-		self.slide_by(row_range, col_range, row_step, col_step, reporting);
+		self.slide(Direction::East, reporting);
 	}
 
 	/// Slides all the movable rocks north, then west, then south, then east.
@@ -127,39 +141,153 @@ impl Board {
 
 	/// Spins the board A BILLION! times.
 	pub fn spin_many(&mut self, reporting: bool) {
-		let total_spins = 1_000_000_000;
-		let mut previous_boards = vec![self.clone()];
-		for spin_count in 1..=total_spins {
+		self.spin_n(1_000_000_000, reporting);
+	}
+
+	/// Spins the board `total_spins` times, skipping ahead once it starts cycling.
+	/// Returns the spin count at which a repeat was found and the cycle length, if there was one.
+	pub fn spin_n(&mut self, total_spins: usize, reporting: bool) -> Option<(usize, usize)> {
+		let Some((offset, cycle_length)) = self.find_spin_cycle(total_spins) else {
+			for _ in 0..total_spins {
+				self.spin_once(reporting);
+			}
+			return None;
+		};
+
+		let spin_count = offset + cycle_length;
+		let remaining_spins = (total_spins - spin_count) % cycle_length;
+		eprintln!("Stable after {spin_count} spins. Cycle length: {cycle_length}. Need {remaining_spins} to match end state.");
+		// Go into the cycle, then straight to the end state's place in it.
+		for _ in 0..offset + remaining_spins {
 			self.spin_once(reporting);
-			let duplicate = previous_boards.iter().position(|board| board == self);
-			if let Some(dup_spin_count) = duplicate {
-				let cycle_length = spin_count - dup_spin_count;
+		}
+
+		Some((spin_count, cycle_length))
+	}
 
-				let remaining_spins = (total_spins - spin_count) % cycle_length;
-				println!("Stable after {spin_count} spins. Cycle length: {cycle_length}. Need {remaining_spins} to match end state.");
+	/// Finds the offset and length of the cycle spinning falls into, if it's repeated a board within `max_spins` spins.
+	#[must_use]
+	fn find_spin_cycle(&self, max_spins: usize) -> Option<(usize, usize)> {
+		self.find_spin_cycle_with(max_spins, Board::board_fingerprint)
+	}
 
-				for _ in 0..remaining_spins {
-					// Could just read it from `previous_boards`, but I enjoy watching it go.
-					self.spin_once(reporting);
-				}
+	/// Finds the spin cycle, using `fingerprint` to tell boards apart cheaply.
+	/// Boards are compared in full only when their fingerprints match, so collisions can't fake a cycle.
+	#[must_use]
+	fn find_spin_cycle_with(
+		&self,
+		max_spins: usize,
+		fingerprint: fn(&Board) -> u64,
+	) -> Option<(usize, usize)> {
+		// Tuples compare the fingerprint before the board
+		detect_cycle_within(
+			|(hash, board): &mut (u64, Board)| {
+				board.spin_once(false);
+				*hash = fingerprint(board);
+			},
+			(fingerprint(self), self.clone()),
+			max_spins,
+		)
+	}
+
+	/// Spins the board `n` times with no cycle shortcut, recording the north load after each spin.
+	#[cfg(test)]
+	pub fn spin_load_history(&mut self, n: usize) -> Vec<usize> {
+		(0..n)
+			.map(|_| {
+				self.spin_once(false);
+				self.get_load()
+			})
+			.collect()
+	}
 
-				return;
-			}
-			previous_boards.push(self.clone());
+	/// Gets a copy of this board spun `n` times, with no cycle detection.
+	#[cfg(test)]
+	#[must_use]
+	fn replay_spins(&self, n: usize) -> Board {
+		let mut board = self.clone();
+		for _ in 0..n {
+			board.spin_once(false);
 		}
+		board
+	}
+
+	/// Gets the board state after `n` spins, without printing or changing this board.
+	/// Reads the end state from the cycle, so large `n` only costs one cycle.
+	#[cfg(test)]
+	#[must_use]
+	pub fn board_after_n_spins(&self, n: usize) -> Board {
+		let spins = match self.find_spin_cycle(n) {
+			Some((offset, cycle_length)) => offset + (n - offset) % cycle_length,
+			None => n,
+		};
+		self.replay_spins(spins)
+	}
+
+	/// Hashes the board's tiles. Distinct boards may share a fingerprint.
+	#[must_use]
+	pub fn board_fingerprint(&self) -> u64 {
+		let mut hasher = DefaultHasher::new();
+		self.hash(&mut hasher);
+		hasher.finish()
 	}
 
 	/// Computes the load from the position of rocks.
 	pub fn get_load(&self) -> usize {
-		(0..self.nb_rows)
-			.map(|row_index| {
-				(self.nb_rows - row_index)
-					* self.tiles[row_index]
-						.iter()
-						.filter(|&&tile| tile == Tile::Movable)
-						.count()
-			})
-			.sum()
+		// Load on the north beams grows with distance from the south edge.
+		self.get_load_dir(Direction::South)
+	}
+
+	/// Computes a load weighting each rock by its distance from the given edge, counting its own row or column.
+	#[must_use]
+	pub fn get_load_dir(&self, direction: Direction) -> usize {
+		let mut load = 0;
+		for (row_index, row) in self.tiles.iter().enumerate() {
+			for (col_index, &tile) in row.iter().enumerate() {
+				if tile == Tile::Movable {
+					load += match direction {
+						Direction::North => row_index + 1,
+						Direction::South => self.nb_rows - row_index,
+						Direction::West => col_index + 1,
+						Direction::East => self.nb_cols - col_index,
+					};
+				}
+			}
+		}
+		load
+	}
+
+	/// Counts movable rocks.
+	#[cfg(test)]
+	#[must_use]
+	pub fn movable_count(&self) -> usize {
+		self.tiles
+			.iter()
+			.flatten()
+			.filter(|&&tile| tile == Tile::Movable)
+			.count()
+	}
+
+	/// Gets the mean (row, column) position of movable rocks.
+	/// Both coordinates are NaN if there are no movable rocks.
+	#[cfg(test)]
+	#[must_use]
+	pub fn center_of_mass(&self) -> (f64, f64) {
+		let mut row_sum = 0;
+		let mut col_sum = 0;
+		for (row_index, row) in self.tiles.iter().enumerate() {
+			for (col_index, &tile) in row.iter().enumerate() {
+				if tile == Tile::Movable {
+					row_sum += row_index;
+					col_sum += col_index;
+				}
+			}
+		}
+		#[allow(clippy::cast_precision_loss)]
+		let nb_movables = self.movable_count() as f64;
+		#[allow(clippy::cast_precision_loss)]
+		let center = (row_sum as f64 / nb_movables, col_sum as f64 / nb_movables);
+		center
 	}
 }
 
@@ -176,6 +304,216 @@ impl fmt::Display for Board {
 	}
 }
 
+#[cfg(test)]
+mod test_spin_n {
+	use crate::parse_input::parse_full;
+
+	#[test]
+	fn test_sample_3_spins() {
+		let mut board = parse_full(include_str!("../input_sample.txt"));
+		board.spin_n(3, false);
+		assert_eq!(
+			board,
+			parse_full(include_str!("../sample_after_3_spins.txt"))
+		);
+	}
+
+	#[test]
+	fn test_sample_cycle_detection() {
+		let mut board = parse_full(include_str!("../input_sample.txt"));
+		assert_eq!(board.spin_n(1_000_000_000, false), Some((10, 7)));
+	}
+
+	#[test]
+	fn test_too_few_spins_to_cycle() {
+		let mut board = parse_full(include_str!("../input_sample.txt"));
+		assert_eq!(board.spin_n(5, false), None);
+	}
+
+	#[test]
+	fn test_board_after_1_spin() {
+		let board = parse_full(include_str!("../input_sample.txt"));
+		assert_eq!(
+			board.board_after_n_spins(1),
+			parse_full(include_str!("../sample_after_1_spin.txt"))
+		);
+	}
+
+	#[test]
+	fn test_board_after_n_spins_matches_spin_n() {
+		let board = parse_full(include_str!("../input_sample.txt"));
+		for n in [0, 3, 10, 17, 1_000_000_000] {
+			let mut expected = board.clone();
+			expected.spin_n(n, false);
+			assert_eq!(board.board_after_n_spins(n), expected);
+		}
+	}
+
+	#[test]
+	fn test_fingerprint() {
+		let board = parse_full(include_str!("../input_sample.txt"));
+		let mut spun = board.clone();
+		spun.spin_once(false);
+		assert_eq!(board.board_fingerprint(), board.clone().board_fingerprint());
+		assert_ne!(board.board_fingerprint(), spun.board_fingerprint());
+	}
+
+	#[test]
+	fn test_fingerprint_collisions() {
+		// Every board collides, so only the full comparison can tell them apart.
+		let board = parse_full(include_str!("../input_sample.txt"));
+		assert_eq!(
+			board.find_spin_cycle_with(1_000_000_000, |_| 0),
+			Some((3, 7))
+		);
+		assert_eq!(board.find_spin_cycle(1_000_000_000), Some((3, 7)));
+	}
+
+	#[test]
+	fn test_load_history() {
+		let mut board = parse_full(include_str!("../input_sample.txt"));
+		assert_eq!(board.spin_load_history(3), vec![87, 69, 69]);
+		assert_eq!(
+			board,
+			parse_full(include_str!("../sample_after_3_spins.txt"))
+		);
+		assert_eq!(board.spin_load_history(0), vec![]);
+	}
+
+	#[test]
+	fn test_no_spins() {
+		let mut board = parse_full(include_str!("../input_sample.txt"));
+		board.spin_n(0, false);
+		assert_eq!(board, parse_full(include_str!("../input_sample.txt")));
+	}
+}
+
+#[cfg(test)]
+mod test_slide {
+	use super::Tile::{Empty, Movable, Wall};
+	use super::*;
+
+	#[test]
+	fn test_north_matches_slide_north() {
+		let cases = [
+			(
+				Board::from(vec![vec![Empty; 3], vec![Empty; 3]]),
+				Board::from(vec![vec![Empty; 3], vec![Empty; 3]]),
+			),
+			(
+				Board::from(vec![
+					vec![Empty; 3],
+					vec![Movable, Empty, Empty],
+					vec![Empty, Movable, Empty],
+				]),
+				Board::from(vec![
+					vec![Movable, Movable, Empty],
+					vec![Empty; 3],
+					vec![Empty; 3],
+				]),
+			),
+			(
+				Board::from(vec![
+					vec![Wall, Empty],
+					vec![Empty, Wall],
+					vec![Movable, Movable],
+				]),
+				Board::from(vec![
+					vec![Wall, Empty],
+					vec![Movable, Wall],
+					vec![Empty, Movable],
+				]),
+			),
+			(
+				Board::from(vec![
+					vec![Movable, Wall],
+					vec![Empty, Movable],
+					vec![Empty, Empty],
+					vec![Movable, Movable],
+				]),
+				Board::from(vec![
+					vec![Movable, Wall],
+					vec![Movable, Movable],
+					vec![Empty, Movable],
+					vec![Empty, Empty],
+				]),
+			),
+			(
+				Board::from(vec![
+					vec![Empty],
+					vec![Empty],
+					vec![Movable],
+					vec![Movable],
+					vec![Empty],
+					vec![Movable],
+				]),
+				Board::from(vec![
+					vec![Movable],
+					vec![Movable],
+					vec![Movable],
+					vec![Empty],
+					vec![Empty],
+					vec![Empty],
+				]),
+			),
+		];
+		for (mut board, expected) in cases {
+			board.slide(Direction::North, false);
+			assert_eq!(board, expected);
+		}
+	}
+
+	#[test]
+	fn test_step_by_step() {
+		let board = Board::from(vec![
+			vec![Empty, Wall],
+			vec![Empty, Empty],
+			vec![Movable, Empty],
+			vec![Movable, Movable],
+		]);
+		let mut expected = board.clone();
+		expected.slide_north(false);
+
+		let mut stepped = board;
+		assert!(stepped.slide_step(Direction::North));
+		assert_eq!(
+			stepped,
+			Board::from(vec![
+				vec![Empty, Wall],
+				vec![Movable, Empty],
+				vec![Movable, Movable],
+				vec![Empty, Empty],
+			])
+		);
+		let mut nb_steps = 1;
+		while stepped.slide_step(Direction::North) {
+			nb_steps += 1;
+		}
+		assert_eq!(nb_steps, 2);
+		assert_eq!(stepped, expected);
+	}
+
+	#[test]
+	fn test_each_direction() {
+		let board = Board::from(vec![
+			vec![Empty, Empty, Empty],
+			vec![Empty, Movable, Empty],
+			vec![Empty, Empty, Empty],
+		]);
+		let cases = [
+			(Direction::North, (0, 1)),
+			(Direction::South, (2, 1)),
+			(Direction::West, (1, 0)),
+			(Direction::East, (1, 2)),
+		];
+		for (direction, (row, col)) in cases {
+			let mut slid = board.clone();
+			slid.slide(direction, false);
+			assert_eq!(slid.tiles[row][col], Movable);
+		}
+	}
+}
+
 #[cfg(test)]
 mod test_slide_north {
 	use super::Tile::{Empty, Movable, Wall};
@@ -260,6 +598,19 @@ mod test_slide_north {
 		board.slide_north(false);
 		assert_eq!(board, expected);
 	}
+
+	#[test]
+	fn test_tall_column() {
+		let tiles: Vec<Vec<Tile>> = (0..1000)
+			.map(|row| vec![if row % 2 == 0 { Empty } else { Movable }])
+			.collect();
+		let mut board = Board::from(tiles);
+		let expected_tiles: Vec<Vec<Tile>> = (0..1000)
+			.map(|row| vec![if row < 500 { Movable } else { Empty }])
+			.collect();
+		board.slide_north(false);
+		assert_eq!(board, Board::from(expected_tiles));
+	}
 }
 
 #[cfg(test)]
@@ -502,4 +853,64 @@ mod test_load {
 		let board = Board::from(vec![vec![Movable; 3]]);
 		assert_eq!(board.get_load(), 3);
 	}
+
+	#[test]
+	fn test_dir_top() {
+		let board = Board::from(vec![
+			vec![Empty, Movable, Empty],
+			vec![Empty; 3],
+			vec![Empty; 3],
+			vec![Empty; 3],
+		]);
+		assert_eq!(board.get_load_dir(Direction::South), board.nb_rows);
+		assert_eq!(board.get_load_dir(Direction::North), 1);
+		assert_eq!(board.get_load_dir(Direction::West), 2);
+		assert_eq!(board.get_load_dir(Direction::East), 2);
+	}
+
+	#[test]
+	fn test_dir_matches_load() {
+		let board = Board::from(vec![
+			vec![Movable, Wall],
+			vec![Empty, Movable],
+			vec![Movable, Movable],
+		]);
+		assert_eq!(board.get_load_dir(Direction::South), board.get_load());
+		assert_eq!(board.get_load_dir(Direction::East), 2 + 1 + 2 + 1);
+	}
+}
+
+#[cfg(test)]
+mod test_center_of_mass {
+	use super::Tile::{Empty, Movable, Wall};
+	use super::*;
+
+	#[test]
+	fn test_small_board() {
+		let board = Board::from(vec![
+			vec![Movable, Wall, Empty],
+			vec![Empty, Empty, Movable],
+			vec![Empty, Movable, Wall],
+		]);
+		assert_eq!(board.movable_count(), 3);
+		let (row, col) = board.center_of_mass();
+		assert!((row - 1.).abs() < 1e-12);
+		assert!((col - 1.).abs() < 1e-12);
+	}
+
+	#[test]
+	fn test_no_movables() {
+		let board = Board::from(vec![vec![Wall, Empty]]);
+		assert_eq!(board.movable_count(), 0);
+		let (row, col) = board.center_of_mass();
+		assert!(row.is_nan() && col.is_nan());
+	}
+
+	#[test]
+	fn test_settles_after_slide() {
+		let mut board = Board::from(vec![vec![Empty], vec![Empty], vec![Movable]]);
+		board.slide_north(false);
+		assert_eq!(board.movable_count(), 1);
+		assert_eq!(board.center_of_mass(), (0., 0.));
+	}
 }
@@ -1,5 +1,6 @@
-use std::io::{self, Read};
+use std::env;
 
+use common::{emit, has_flag, read_input, timed};
 use parse_input::parse_full;
 
 mod board;
@@ -9,7 +10,9 @@ mod pretty;
 #[must_use]
 fn load_after_slide(input: &str, reporting: bool) -> usize {
 	let mut board = parse_full(input);
-	println!("{board}");
+	if reporting {
+		println!("{board}");
+	}
 	board.slide_north(reporting);
 	board.get_load()
 }
@@ -58,14 +61,19 @@ mod test {
 }
 
 fn main() {
-	let mut input = String::new();
-	io::stdin()
-		.read_to_string(&mut input)
-		.expect("Failed to read input");
+	let input = read_input(env::args().skip(1)).expect("Failed to read input");
 
-	println!("Part 1 — slide once: {}", load_after_slide(&input, true));
-	println!(
-		"Part 2 — spin a billion times: {}",
-		load_after_spins(&input, true)
+	let json = has_flag("--json");
+	let reporting = !json && !has_flag("--quiet");
+	emit(
+		(
+			"Part 1 — slide once",
+			timed("Part 1", || load_after_slide(&input, reporting)),
+		),
+		(
+			"Part 2 — spin a billion times",
+			timed("Part 2", || load_after_spins(&input, reporting)),
+		),
+		json,
 	);
 }
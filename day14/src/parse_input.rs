@@ -1,26 +1,30 @@
+use common::{parse_tile_grid, GridError};
+
 use crate::board::{Board, Tile};
 
-/// Reads one tile.
-#[must_use]
-fn read_tile(c: char) -> Tile {
+/// Reads one tile, if it's `.`, `#` or `O`.
+fn read_tile(c: char) -> Option<Tile> {
 	match c {
-		'.' => Tile::Empty,
-		'#' => Tile::Wall,
-		'O' => Tile::Movable,
-		_ => unreachable!(),
+		'.' => Some(Tile::Empty),
+		'#' => Some(Tile::Wall),
+		'O' => Some(Tile::Movable),
+		_ => None,
 	}
 }
 
+/// Parses the whole input.
+/// # Errors
+/// On an unknown tile, rows of different lengths, or an empty input.
+pub fn try_parse_full(input: &str) -> Result<Board, GridError> {
+	Ok(Board::from(parse_tile_grid(input, read_tile)?.into_rows()))
+}
+
 /// Parses the whole input.
 /// # Panics
 /// On any parse error.
 #[must_use]
 pub fn parse_full(input: &str) -> Board {
-	let tiles: Vec<Vec<Tile>> = input
-		.lines()
-		.map(|line| line.chars().map(read_tile).collect())
-		.collect();
-	Board::from(tiles)
+	try_parse_full(input).unwrap_or_else(|error| panic!("Parse error: {error}"))
 }
 
 #[cfg(test)]
@@ -34,4 +38,34 @@ mod test {
 		let expected = Board::from(vec![vec![Empty; 3], vec![Wall, Movable, Wall]]);
 		assert_eq!(parse_full(input), expected);
 	}
+
+	#[test]
+	fn test_invalid_tile() {
+		assert_eq!(
+			try_parse_full("...\n#?#\n"),
+			Err(GridError::InvalidTile {
+				row: 1,
+				col: 1,
+				tile: '?'
+			})
+		);
+	}
+
+	#[test]
+	fn test_ragged_rows() {
+		assert_eq!(
+			try_parse_full("...\n#O\n..."),
+			Err(GridError::Ragged {
+				row: 1,
+				expected: 3,
+				found: 2
+			})
+		);
+	}
+
+	#[test]
+	fn test_empty() {
+		assert_eq!(try_parse_full(""), Err(GridError::Empty));
+		assert_eq!(try_parse_full("\n"), Err(GridError::Empty));
+	}
 }
@@ -0,0 +1,14 @@
+use common::harness::run_sample;
+
+#[test]
+fn test_sample() {
+	assert_eq!(
+		run_sample(
+			env!("CARGO_BIN_EXE_day14"),
+			env!("CARGO_MANIFEST_DIR"),
+			"input_sample.txt",
+			&["--quiet"]
+		),
+		"Part 1 — slide once: 136\nPart 2 — spin a billion times: 64\n"
+	);
+}
@@ -1,14 +1,21 @@
 use std::array;
+use std::fmt;
 
 use crate::hash::{get_hash, SIZE};
+use crate::instruction::{execute, Instruction};
 
 /// World's stupidest hashmap implementation.
+///
+/// Each bucket keeps its pairs in order, which `get_power` depends on:
+/// - setting an existing key replaces its value in the same slot,
+/// - setting a new key appends it after the others,
+/// - removing a key shifts the later keys down one slot.
 #[derive(Debug, Clone, Eq, Hash, Ord, PartialEq, PartialOrd)]
-pub struct BucketList {
-	buckets: [Bucket; SIZE],
+pub struct BucketList<V> {
+	buckets: [Bucket<V>; SIZE],
 }
 
-impl BucketList {
+impl<V> BucketList<V> {
 	/// Creates an empty map.
 	pub fn new() -> Self {
 		BucketList {
@@ -17,18 +24,69 @@ impl BucketList {
 	}
 
 	/// Lists the values in one bucket.
-	pub fn get_bucket_values(&self, bucket_index: usize) -> Vec<usize> {
+	pub fn get_bucket_values(&self, bucket_index: usize) -> Vec<V>
+	where
+		V: Clone,
+	{
 		self.buckets[bucket_index].get_values()
 	}
 
+	/// Iterates over every key-value pair, bucket by bucket, then slot by slot.
+	#[cfg(test)]
+	pub fn entries(&self) -> impl Iterator<Item = (&str, &V)> + '_ {
+		self.buckets
+			.iter()
+			.flat_map(|bucket| bucket.slots.iter().map(|(k, v)| (k.as_str(), v)))
+	}
+
+	/// Gets the value for a key, if it exists.
+	#[cfg(test)]
+	pub fn get(&self, key: &str) -> Option<&V> {
+		self.buckets[get_hash(key)].get(key)
+	}
+
+	/// Checks whether a key exists.
+	#[cfg(test)]
+	pub fn contains_key(&self, key: &str) -> bool {
+		self.get(key).is_some()
+	}
+
+	/// Gets the position of a key within its bucket, if it exists.
+	#[cfg(test)]
+	pub fn slot_index(&self, key: &str) -> Option<usize> {
+		self.buckets[get_hash(key)].slot_index(key)
+	}
+
+	/// Counts the key-value pairs across all buckets.
+	#[cfg(test)]
+	pub fn len(&self) -> usize {
+		self.buckets.iter().map(|bucket| bucket.slots.len()).sum()
+	}
+
+	/// Checks whether there are no key-value pairs at all.
+	#[cfg(test)]
+	pub fn is_empty(&self) -> bool {
+		self.buckets.iter().all(|bucket| bucket.slots.is_empty())
+	}
+
 	/// Sets a key-value pair, overwriting the value if the key exists.
-	pub fn set(&mut self, key: &str, value: usize) {
+	pub fn set(&mut self, key: &str, value: V) {
 		self.buckets[get_hash(key)].set(key, value);
 	}
 
 	/// Removes a key-value pair. Does nothing if the key doesn't exist.
-	pub fn remove(&mut self, key: &str) {
-		self.buckets[get_hash(key)].remove(key);
+	/// Returns whether a pair was removed.
+	pub fn remove(&mut self, key: &str) -> bool {
+		self.buckets[get_hash(key)].remove(key)
+	}
+}
+
+impl BucketList<usize> {
+	/// Executes instructions in order.
+	pub fn run(&mut self, instructions: &[Instruction]) {
+		for instruction in instructions {
+			execute(self, instruction);
+		}
 	}
 
 	/// Summarises all the values.
@@ -48,26 +106,57 @@ impl BucketList {
 	}
 }
 
+/// Lists the non-empty boxes in the puzzle's format, e.g. `Box 0: [rn 1] [cm 2]`.
+impl<V: fmt::Display> fmt::Display for BucketList<V> {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		for (bucket_index, bucket) in self.buckets.iter().enumerate() {
+			if bucket.slots.is_empty() {
+				continue;
+			}
+			write!(f, "Box {bucket_index}:")?;
+			for (key, value) in &bucket.slots {
+				write!(f, " [{key} {value}]")?;
+			}
+			writeln!(f)?;
+		}
+		Ok(())
+	}
+}
+
 /// A bucket for a given hash.
 #[derive(Debug, Clone, Eq, Hash, Ord, PartialEq, PartialOrd)]
-struct Bucket {
-	slots: Vec<KeyValue>,
+struct Bucket<V> {
+	slots: Vec<KeyValue<V>>,
 }
 
-impl Bucket {
+impl<V> Bucket<V> {
 	fn new() -> Self {
 		Self { slots: vec![] }
 	}
 
 	/// Gets the values of the pairs in the bucket, in order.
-	fn get_values(&self) -> Vec<usize> {
-		self.slots.iter().map(|(_, v)| *v).collect()
+	fn get_values(&self) -> Vec<V>
+	where
+		V: Clone,
+	{
+		self.slots.iter().map(|(_, v)| v.clone()).collect()
+	}
+
+	/// Gets the position of a key in the bucket, if it's there.
+	fn slot_index(&self, key: &str) -> Option<usize> {
+		self.slots.iter().position(|(k, _)| k == key)
+	}
+
+	/// Gets the value for a key, if it's in the bucket.
+	#[cfg(test)]
+	fn get(&self, key: &str) -> Option<&V> {
+		self.slot_index(key).map(|index| &self.slots[index].1)
 	}
 
 	/// Adds a key-value pair: replaces the existing pair if any, otherwise appends.
-	fn set(&mut self, key: &str, value: usize) {
-		let pair: KeyValue = (String::from(key), value);
-		if let Some(index) = self.slots.iter().position(|(k, _)| k == key) {
+	fn set(&mut self, key: &str, value: V) {
+		let pair: KeyValue<V> = (String::from(key), value);
+		if let Some(index) = self.slot_index(key) {
 			self.slots[index] = pair;
 		} else {
 			self.slots.push(pair);
@@ -75,20 +164,25 @@ impl Bucket {
 	}
 
 	/// Removes a key-value pair. Does nothing if the key doesn't exist.
-	pub fn remove(&mut self, key: &str) {
+	/// Returns whether a pair was removed.
+	pub fn remove(&mut self, key: &str) -> bool {
+		let nb_slots = self.slots.len();
 		self.slots.retain(|(k, _)| k != key);
+		self.slots.len() != nb_slots
 	}
 }
 
-type KeyValue = (String, usize);
+type KeyValue<V> = (String, V);
 
 #[cfg(test)]
 mod test {
 	use super::*;
+	use crate::instruction::execute;
+	use crate::parse_input::{parse_instruction, to_steps};
 
 	#[test]
 	fn test_empty() {
-		let list = BucketList::new();
+		let list: BucketList<usize> = BucketList::new();
 		for bucket_index in 0..SIZE {
 			assert_eq!(list.get_bucket_values(bucket_index), vec![]);
 		}
@@ -119,10 +213,36 @@ mod test {
 		let mut list = BucketList::new();
 		list.set("pc", 42);
 		assert_eq!(list.get_bucket_values(3), vec![42]);
-		list.remove("pc");
+		assert!(list.remove("pc"));
 		assert_eq!(list.get_bucket_values(3), vec![]);
 	}
 
+	#[test]
+	fn test_remove_missing() {
+		let mut list = BucketList::new();
+		assert!(!list.remove("pc"));
+		list.set("ot", 42);
+		assert!(!list.remove("pc"));
+		assert!(list.remove("ot"));
+		assert!(!list.remove("ot"));
+	}
+
+	#[test]
+	fn test_get() {
+		let mut list = BucketList::new();
+		assert_eq!(list.get("pc"), None);
+		assert!(!list.contains_key("pc"));
+		list.set("pc", 42);
+		list.set("ot", 7);
+		assert_eq!(list.get("pc"), Some(&42));
+		assert_eq!(list.get("ot"), Some(&7));
+		assert!(list.contains_key("pc"));
+		list.remove("pc");
+		assert_eq!(list.get("pc"), None);
+		assert!(!list.contains_key("pc"));
+		assert_eq!(list.get("ot"), Some(&7));
+	}
+
 	#[test]
 	fn test_add_in_order() {
 		let mut list = BucketList::new();
@@ -142,6 +262,17 @@ mod test {
 		assert_eq!(list.get_bucket_values(3), vec![1, 999, 3]);
 	}
 
+	#[test]
+	fn test_entries() {
+		let mut list = BucketList::new();
+		assert_eq!(list.entries().count(), 0);
+		list.set("rn", 1);
+		list.set("cm", 2);
+		list.set("qp", 3);
+		let entries: Vec<(&str, &usize)> = list.entries().collect();
+		assert_eq!(entries, vec![("rn", &1), ("cm", &2), ("qp", &3)]);
+	}
+
 	#[test]
 	fn test_remove_preserves_order() {
 		let mut list = BucketList::new();
@@ -151,4 +282,76 @@ mod test {
 		list.remove("ot");
 		assert_eq!(list.get_bucket_values(3), vec![1, 3]);
 	}
+
+	#[test]
+	fn test_slot_index_replace_keeps_slot() {
+		let mut list = BucketList::new();
+		list.set("pc", 1);
+		list.set("ot", 2);
+		list.set("ab", 3);
+		assert_eq!(list.slot_index("ot"), Some(1));
+		list.set("ot", 999);
+		assert_eq!(list.slot_index("pc"), Some(0));
+		assert_eq!(list.slot_index("ot"), Some(1));
+		assert_eq!(list.slot_index("ab"), Some(2));
+	}
+
+	#[test]
+	fn test_slot_index_remove_compacts() {
+		let mut list = BucketList::new();
+		list.set("pc", 1);
+		list.set("ot", 2);
+		list.set("ab", 3);
+		list.remove("pc");
+		assert_eq!(list.slot_index("pc"), None);
+		assert_eq!(list.slot_index("ot"), Some(0));
+		assert_eq!(list.slot_index("ab"), Some(1));
+		list.set("pc", 4);
+		assert_eq!(list.slot_index("pc"), Some(2));
+	}
+
+	#[test]
+	fn test_len() {
+		let mut list = BucketList::new();
+		assert_eq!(list.len(), 0);
+		assert!(list.is_empty());
+		list.set("rn", 1);
+		list.set("cm", 2);
+		list.set("pc", 3);
+		list.set("rn", 4);
+		assert_eq!(list.len(), 3);
+		assert!(!list.is_empty());
+		list.remove("cm");
+		list.remove("qp");
+		assert_eq!(list.len(), 2);
+		list.remove("rn");
+		list.remove("pc");
+		assert_eq!(list.len(), 0);
+		assert!(list.is_empty());
+	}
+
+	#[test]
+	fn test_display_sample() {
+		let mut list = BucketList::new();
+		assert_eq!(list.to_string(), "");
+		for step in to_steps(include_str!("../input_sample.txt")) {
+			execute(&mut list, &parse_instruction(&step));
+		}
+		assert_eq!(
+			list.to_string(),
+			"Box 0: [rn 1] [cm 2]\nBox 3: [ot 7] [ab 5] [pc 6]\n"
+		);
+	}
+
+	#[test]
+	fn test_str_values() {
+		let mut list = BucketList::new();
+		list.set("rn", "one");
+		list.set("cm", "two");
+		list.set("rn", "three");
+		assert_eq!(list.get("rn"), Some(&"three"));
+		assert_eq!(list.get_bucket_values(0), vec!["three", "two"]);
+		assert!(list.remove("cm"));
+		assert_eq!(list.get("cm"), None);
+	}
 }
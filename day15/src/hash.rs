@@ -4,13 +4,27 @@ pub const SIZE: usize = 256;
 #[must_use]
 #[allow(clippy::module_name_repetitions)]
 pub fn get_hash(input: &str) -> usize {
-	let mut hash = 0;
-	for ch in input.bytes() {
-		hash += usize::from(ch);
-		hash *= 17;
-		hash %= SIZE;
-	}
-	hash
+	input.bytes().fold(0, hash_step)
+}
+
+/// Runs the hash function, also listing the running value after each character.
+#[cfg(test)]
+#[must_use]
+pub fn hash_with_trace(input: &str) -> (usize, Vec<usize>) {
+	let trace: Vec<usize> = input
+		.bytes()
+		.scan(0, |hash, ch| {
+			*hash = hash_step(*hash, ch);
+			Some(*hash)
+		})
+		.collect();
+	(trace.last().copied().unwrap_or(0), trace)
+}
+
+/// Feeds one character into the hash.
+#[must_use]
+fn hash_step(hash: usize, ch: u8) -> usize {
+	(hash + usize::from(ch)) * 17 % SIZE
 }
 
 #[cfg(test)]
@@ -47,4 +61,11 @@ mod test {
 		assert_eq!(get_hash("pc=6"), 214);
 		assert_eq!(get_hash("ot=7"), 231);
 	}
+
+	#[test]
+	fn test_trace() {
+		assert_eq!(hash_with_trace("HASH"), (52, vec![200, 153, 172, 52]));
+		assert_eq!(hash_with_trace(""), (0, vec![]));
+		assert_eq!(hash_with_trace("rn=1").0, get_hash("rn=1"));
+	}
 }
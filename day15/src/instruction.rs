@@ -7,16 +7,19 @@ pub enum Instruction {
 	Remove(String),
 }
 
-pub fn execute(list: &mut BucketList, instruction: &Instruction) {
+pub fn execute(list: &mut BucketList<usize>, instruction: &Instruction) {
 	match instruction {
 		Instruction::Set(key, value) => list.set(key, *value),
-		Instruction::Remove(key) => list.remove(key),
+		Instruction::Remove(key) => {
+			list.remove(key);
+		}
 	}
 }
 
 #[cfg(test)]
 mod test {
 	use super::*;
+	use crate::parse_input::{parse_instruction, to_steps};
 
 	#[test]
 	fn test_execute() {
@@ -31,4 +34,25 @@ mod test {
 		expected.remove("foobar");
 		assert_eq!(list, expected);
 	}
+
+	#[test]
+	fn test_large_focal_length() {
+		let mut list = BucketList::new();
+		execute(&mut list, &Instruction::Set(String::from("ab"), 15));
+		// "ab" hashes to box 3: (3 + 1) * slot 1 * 15
+		assert_eq!(list.get_power(), 60);
+		execute(&mut list, &Instruction::Set(String::from("pc"), 100));
+		assert_eq!(list.get_power(), 60 + 4 * 2 * 100);
+	}
+
+	#[test]
+	fn test_run_sample() {
+		let instructions: Vec<Instruction> = to_steps(include_str!("../input_sample.txt"))
+			.iter()
+			.map(|step| parse_instruction(step))
+			.collect();
+		let mut list = BucketList::new();
+		list.run(&instructions);
+		assert_eq!(list.get_power(), 145);
+	}
 }
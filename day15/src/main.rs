@@ -1,8 +1,9 @@
-use std::io::{self, Read};
+use std::env;
 
 use buckets::BucketList;
+use common::{emit, has_flag, read_input, timed};
 use hash::get_hash;
-use instruction::execute;
+use instruction::Instruction;
 use parse_input::{parse_instruction, to_steps};
 
 mod buckets;
@@ -10,18 +11,32 @@ mod hash;
 mod instruction;
 mod parse_input;
 
+#[cfg(any(test, not(feature = "parallel")))]
 #[must_use]
 fn get_hash_sum(input: &str) -> usize {
 	to_steps(input).into_iter().map(|s| get_hash(&s)).sum()
 }
 
+/// Sums step hashes on several threads, splitting steps into one chunk per available core.
+#[cfg(feature = "parallel")]
+#[must_use]
+fn get_hash_sum_parallel(input: &str) -> usize {
+	let steps = to_steps(input);
+	common::map_chunks(&steps, |chunk| {
+		chunk.iter().map(|s| get_hash(s)).sum::<usize>()
+	})
+	.into_iter()
+	.sum()
+}
+
 #[must_use]
 fn get_power(input: &str) -> usize {
-	let intructions = to_steps(input).into_iter().map(|s| parse_instruction(&s));
+	let instructions: Vec<Instruction> = to_steps(input)
+		.into_iter()
+		.map(|s| parse_instruction(&s))
+		.collect();
 	let mut bucket_list = BucketList::new();
-	for instruction in intructions {
-		execute(&mut bucket_list, &instruction);
-	}
+	bucket_list.run(&instructions);
 	bucket_list.get_power()
 }
 
@@ -35,6 +50,13 @@ mod test {
 		assert_eq!(get_hash_sum(SAMPLE_INPUT), 1320);
 	}
 
+	#[cfg(feature = "parallel")]
+	#[test]
+	fn test_sample_hash_parallel() {
+		assert_eq!(get_hash_sum_parallel(SAMPLE_INPUT), 1320);
+		assert_eq!(get_hash_sum_parallel(""), get_hash_sum(""));
+	}
+
 	#[test]
 	fn test_sample_run() {
 		assert_eq!(get_power(SAMPLE_INPUT), 145);
@@ -42,11 +64,16 @@ mod test {
 }
 
 fn main() {
-	let mut input = String::new();
-	io::stdin()
-		.read_to_string(&mut input)
-		.expect("Failed to read input");
+	let input = read_input(env::args().skip(1)).expect("Failed to read input");
 
-	println!("Part 1: {}", get_hash_sum(&input));
-	println!("Part 2: {}", get_power(&input));
+	let json = has_flag("--json");
+	#[cfg(feature = "parallel")]
+	let part_1 = timed("Part 1", || get_hash_sum_parallel(&input));
+	#[cfg(not(feature = "parallel"))]
+	let part_1 = timed("Part 1", || get_hash_sum(&input));
+	emit(
+		("Part 1", part_1),
+		("Part 2", timed("Part 2", || get_power(&input))),
+		json,
+	);
 }
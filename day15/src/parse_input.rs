@@ -1,7 +1,5 @@
-use nom::branch::alt;
-use nom::bytes::complete::tag;
-use nom::character::complete::{alpha1, u16};
-use nom::combinator::all_consuming;
+use nom::character::complete::{alpha1, digit1};
+use nom::combinator::{all_consuming, map_res};
 use nom::IResult;
 
 use crate::instruction::Instruction;
@@ -16,21 +14,44 @@ pub fn to_steps(input: &str) -> Vec<String> {
 		.collect()
 }
 
-/// Consumes a set instruction.
-fn instruction_set(input: &str) -> IResult<&str, Instruction> {
-	let (input, key) = alpha1(input)?;
-	let (input, _) = tag("=")(input)?;
-	let (input, value) = u16(input)?;
-	let instruction = Instruction::Set(String::from(key), usize::from(value));
-	Ok((input, instruction))
+/// Reasons a step can't be read as an instruction.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum ParseError {
+	/// No `=` or `-` after the label, or something after the `-`.
+	Operation(String),
+	/// A label that's empty or not purely alphabetic.
+	Label(String),
+	/// A focal length that's missing or not a number.
+	FocalLength(String),
 }
 
-/// Consumes a remove instruction.
-fn instruction_remove(input: &str) -> IResult<&str, Instruction> {
-	let (input, key) = alpha1(input)?;
-	let (input, _) = tag("-")(input)?;
-	let instruction = Instruction::Remove(String::from(key));
-	Ok((input, instruction))
+/// Checks that a whole string matches a parser.
+fn matches_fully<'a, O>(
+	parser: impl FnMut(&'a str) -> IResult<&'a str, O>,
+	input: &'a str,
+) -> Option<O> {
+	all_consuming(parser)(input).ok().map(|(_, output)| output)
+}
+
+/// Parses one instruction for the hashmap.
+/// # Errors
+/// On a malformed label, operation or focal length.
+pub fn try_parse_instruction(input: &str) -> Result<Instruction, ParseError> {
+	let op_index = input
+		.find(['=', '-'])
+		.ok_or_else(|| ParseError::Operation(String::from(input)))?;
+	let (label, operation) = input.split_at(op_index);
+	let key = matches_fully(alpha1, label).ok_or_else(|| ParseError::Label(String::from(label)))?;
+	let key = String::from(key);
+	if operation == "-" {
+		return Ok(Instruction::Remove(key));
+	}
+	let focal_length = operation
+		.strip_prefix('=')
+		.ok_or_else(|| ParseError::Operation(String::from(input)))?;
+	let value = matches_fully(map_res(digit1, str::parse::<usize>), focal_length)
+		.ok_or_else(|| ParseError::FocalLength(String::from(focal_length)))?;
+	Ok(Instruction::Set(key, value))
 }
 
 /// Parses one instruction for the hashmap.
@@ -38,9 +59,7 @@ fn instruction_remove(input: &str) -> IResult<&str, Instruction> {
 /// On any parse error.
 #[must_use]
 pub fn parse_instruction(input: &str) -> Instruction {
-	let mut parser = all_consuming(alt((instruction_set, instruction_remove)));
-	let (_, instruction) = parser(input).expect("Invalid instruction");
-	instruction
+	try_parse_instruction(input).expect("Invalid instruction")
 }
 
 #[cfg(test)]
@@ -78,9 +97,53 @@ mod test_parse_instruction {
 		assert_eq!(parse_instruction("foo=6"), expected);
 	}
 
+	#[test]
+	fn test_set_multi_digit() {
+		let expected = Instruction::Set(String::from("ab"), 15);
+		assert_eq!(parse_instruction("ab=15"), expected);
+		let expected = Instruction::Set(String::from("ab"), 1_000_000);
+		assert_eq!(parse_instruction("ab=1000000"), expected);
+	}
+
 	#[test]
 	fn test_remove() {
 		let expected = Instruction::Remove(String::from("yo"));
 		assert_eq!(parse_instruction("yo-"), expected);
 	}
+
+	#[test]
+	fn test_invalid_focal_length() {
+		assert_eq!(
+			try_parse_instruction("ab=x"),
+			Err(ParseError::FocalLength(String::from("x")))
+		);
+		assert_eq!(
+			try_parse_instruction("ab="),
+			Err(ParseError::FocalLength(String::new()))
+		);
+	}
+
+	#[test]
+	fn test_invalid_label() {
+		assert_eq!(
+			try_parse_instruction("a1=3"),
+			Err(ParseError::Label(String::from("a1")))
+		);
+		assert_eq!(
+			try_parse_instruction("-"),
+			Err(ParseError::Label(String::new()))
+		);
+	}
+
+	#[test]
+	fn test_invalid_operation() {
+		assert_eq!(
+			try_parse_instruction("ab"),
+			Err(ParseError::Operation(String::from("ab")))
+		);
+		assert_eq!(
+			try_parse_instruction("ab-3"),
+			Err(ParseError::Operation(String::from("ab-3")))
+		);
+	}
 }
@@ -1,16 +1,11 @@
+#[cfg(test)]
+use std::collections::HashMap;
 use std::{collections::HashSet, fmt};
 
 use self::Direction::{Down, Left, Right, Up};
-use crate::pretty::colourise;
+use common::{colourise, Grid};
 
-/// Direction the beam can move in.
-#[derive(Debug, Clone, Copy, Eq, Hash, Ord, PartialEq, PartialOrd)]
-pub enum Direction {
-	Up,
-	Down,
-	Left,
-	Right,
-}
+pub use common::Direction;
 
 /// A beam entering a position.
 #[derive(Debug, Clone, Copy, Eq, Hash, Ord, PartialEq, PartialOrd)]
@@ -20,6 +15,12 @@ pub struct Beam {
 	pub col: usize,
 }
 
+impl fmt::Display for Beam {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		write!(f, "{:?} at ({}, {})", self.direction, self.row, self.col)
+	}
+}
+
 /// Optical component in a tule.
 #[derive(Debug, Clone, Copy, Eq, Hash, Ord, PartialEq, PartialOrd)]
 pub enum Optics {
@@ -28,6 +29,8 @@ pub enum Optics {
 	MirrorΓ,
 	SplitterH,
 	SplitterV,
+	/// Reflects like `MirrorL`, but also lets the beam through.
+	PartialMirrorL,
 }
 
 /// A tile the beam can traverse.
@@ -70,6 +73,15 @@ impl Tile {
 				Left => Up,
 				Right => Down,
 			}]),
+			Optics::PartialMirrorL => HashSet::from([
+				direction,
+				match direction {
+					Up => Left,
+					Down => Right,
+					Left => Up,
+					Right => Down,
+				},
+			]),
 			Optics::MirrorΓ => HashSet::from([match direction {
 				Up => Right,
 				Down => Left,
@@ -77,12 +89,12 @@ impl Tile {
 				Right => Up,
 			}]),
 			Optics::SplitterH => match direction {
-				Up | Down => HashSet::from([Left, Right]),
+				Up | Down => HashSet::from(direction.turns()),
 				Left | Right => HashSet::from([direction]),
 			},
 			Optics::SplitterV => match direction {
 				Up | Down => HashSet::from([direction]),
-				Left | Right => HashSet::from([Up, Down]),
+				Left | Right => HashSet::from(direction.turns()),
 			},
 		}
 	}
@@ -96,6 +108,7 @@ impl fmt::Display for Tile {
 			Optics::MirrorΓ => '╱',
 			Optics::SplitterH => '─',
 			Optics::SplitterV => '│',
+			Optics::PartialMirrorL => '⋱',
 		};
 		let (background, foreground) = if self.is_lit() { (220, 209) } else { (0, 248) };
 		write!(f, "{}", colourise(symbol, background, foreground))
@@ -105,9 +118,7 @@ impl fmt::Display for Tile {
 /// A 2D array of tiles.
 #[derive(Debug, Clone, Eq, PartialEq)]
 pub struct Board {
-	pub nb_rows: usize,
-	pub nb_cols: usize,
-	pub tiles: Vec<Vec<Tile>>,
+	pub tiles: Grid<Tile>,
 }
 
 impl Board {
@@ -115,87 +126,145 @@ impl Board {
 	#[must_use]
 	pub fn from(tiles: Vec<Vec<Tile>>) -> Self {
 		Self {
-			nb_rows: tiles.len(),
-			nb_cols: tiles.first().expect("Empty grid").len(),
-			tiles,
+			tiles: Grid::from(tiles),
+		}
+	}
+
+	/// Builds a beam entering a position, if that position is on the board.
+	#[must_use]
+	pub fn beam_at(&self, row: usize, col: usize, direction: Direction) -> Option<Beam> {
+		self.tiles.get(row, col).map(|_| Beam {
+			direction,
+			row,
+			col,
+		})
+	}
+
+	/// Removes all beams, leaving only the optics.
+	pub fn clear_beams(&mut self) {
+		for tile in self.tiles.iter_mut() {
+			tile.entering_beams.clear();
 		}
 	}
 
 	/// Counts how many tiles are lit.
 	#[must_use]
 	pub fn count_lit_tiles(&self) -> usize {
+		self.tiles.iter().filter(|tile| tile.is_lit()).count()
+	}
+
+	/// Counts beam activations: each tile counts once per direction a beam entered it.
+	#[cfg(test)]
+	#[must_use]
+	pub fn total_beam_steps(&self) -> usize {
 		self.tiles
 			.iter()
-			.map(|row| row.iter().filter(|tile| tile.is_lit()).count())
+			.map(|tile| tile.entering_beams.len())
 			.sum()
 	}
 
+	/// Counts, for each direction, how many tiles a beam entered going in that direction.
+	#[cfg(test)]
+	#[must_use]
+	pub fn count_lit_by_direction(&self) -> HashMap<Direction, usize> {
+		let mut counts = HashMap::from([(Up, 0), (Down, 0), (Left, 0), (Right, 0)]);
+		for tile in self.tiles.iter() {
+			for direction in &tile.entering_beams {
+				*counts.entry(*direction).or_default() += 1;
+			}
+		}
+		counts
+	}
+
+	/// Lists every beam that went through the board, by position and direction.
+	#[cfg(test)]
+	#[must_use]
+	pub fn energized_beams(&self) -> HashSet<Beam> {
+		let mut beams = HashSet::new();
+		for (row, tiles_row) in self.tiles.iter_rows().enumerate() {
+			for (col, tile) in tiles_row.iter().enumerate() {
+				beams.extend(tile.entering_beams.iter().map(|&direction| Beam {
+					direction,
+					row,
+					col,
+				}));
+			}
+		}
+		beams
+	}
+
+	/// Checks if a beam has already gone through its position in its direction.
+	#[must_use]
+	pub fn has_traversed(&self, beam: Beam) -> bool {
+		self.tiles[beam.row][beam.col]
+			.entering_beams
+			.contains(&beam.direction)
+	}
+
 	/// Propagates a beam by one step. Returns beam(s) at the next step.
 	#[must_use]
 	pub fn propagate(&mut self, beam: Beam) -> HashSet<Beam> {
 		let exit_dirs = self.tiles[beam.row][beam.col].propagate(beam.direction);
-		let mut exit_beams = HashSet::new();
-		for direction in exit_dirs {
-			match direction {
-				Up => {
-					if beam.row > 0 {
-						exit_beams.insert(Beam {
-							row: beam.row - 1,
-							col: beam.col,
-							direction,
-						});
-					}
-				}
-				Down => {
-					if beam.row < self.nb_rows - 1 {
-						exit_beams.insert(Beam {
-							row: beam.row + 1,
-							col: beam.col,
-							direction,
-						});
-					}
-				}
-				Left => {
-					if beam.col > 0 {
-						exit_beams.insert(Beam {
-							row: beam.row,
-							col: beam.col - 1,
-							direction,
-						});
-					}
-				}
-				Right => {
-					if beam.col < self.nb_cols - 1 {
-						exit_beams.insert(Beam {
-							row: beam.row,
-							col: beam.col + 1,
-							direction,
-						});
-					}
-				}
-			}
-		}
-		exit_beams
+		exit_dirs
+			.into_iter()
+			.filter_map(|direction| {
+				let (row, col) = self.tiles.step(beam.row, beam.col, direction.delta())?;
+				Some(Beam {
+					direction,
+					row,
+					col,
+				})
+			})
+			.collect()
 	}
 
 	/// Propagates a beam until no more new beams are produced.
 	pub fn trace(&mut self, initial_beam: Beam) {
-		for exit_beam in self.propagate(initial_beam) {
-			self.trace(exit_beam);
+		self.trace_capped(initial_beam, None)
+			.expect("Uncapped trace can't stop early");
+	}
+
+	/// Traces a beam, then counts how many tiles are lit.
+	pub fn energize(&mut self, beam: Beam) -> usize {
+		self.trace(beam);
+		self.count_lit_tiles()
+	}
+
+	/// Propagates a beam until no more new beams are produced, or `max_states` beams have been processed.
+	/// Returns the number of beams processed.
+	/// # Errors
+	/// If beams are left to process once the cap is reached. Tiles lit so far stay lit.
+	pub fn trace_capped(
+		&mut self,
+		initial_beam: Beam,
+		max_states: Option<usize>,
+	) -> Result<usize, TraceError> {
+		let mut pending = vec![initial_beam];
+		let mut nb_processed = 0;
+		while let Some(beam) = pending.pop() {
+			if let Some(max_states) = max_states {
+				if nb_processed >= max_states {
+					return Err(TraceError::TooManyStates(max_states));
+				}
+			}
+			pending.extend(self.propagate(beam));
+			nb_processed += 1;
 		}
+		Ok(nb_processed)
 	}
 }
 
+/// Reasons a trace stopped early.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum TraceError {
+	/// Processed this many beams without running out.
+	TooManyStates(usize),
+}
+
 impl fmt::Display for Board {
 	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-		let mut res = writeln!(f);
-		for row in &self.tiles {
-			for tile in row {
-				_ = write!(f, "{tile}");
-			}
-			res = writeln!(f);
-		}
-		res
+		writeln!(f)?;
+		write!(f, "{}", self.tiles)
 	}
 }
 
@@ -224,6 +293,7 @@ mod test_propagate {
 	use super::*;
 	use super::{Down, Left, Right, Up};
 	use crate::parse_input::parse_full;
+	use common::sorted;
 
 	#[test]
 	fn test_up_through_empty() {
@@ -729,6 +799,34 @@ mod test_propagate {
 		assert!(board.tiles[1][1].is_lit());
 	}
 
+	#[test]
+	fn test_partial_mirror() {
+		let mut board = parse_full("...\n.%.\n...\n");
+		let beam_in = Beam {
+			row: 1,
+			col: 1,
+			direction: Right,
+		};
+		let beams_out = board.propagate(beam_in);
+
+		assert_eq!(
+			beams_out,
+			HashSet::from([
+				Beam {
+					row: 1,
+					col: 2,
+					direction: Right
+				},
+				Beam {
+					row: 2,
+					col: 1,
+					direction: Down
+				}
+			])
+		);
+		assert!(board.tiles[1][1].is_lit());
+	}
+
 	#[test]
 	fn test_split_into_edge() {
 		let mut board = parse_full("..\n.-\n..\n");
@@ -811,6 +909,100 @@ mod test_propagate {
 
 		assert!(board.tiles[1][1].is_lit());
 	}
+
+	/// Propagates a beam into a 3×3 board with a single splitter where the beam enters.
+	fn split_at(splitter: char, row: usize, col: usize, direction: Direction) -> HashSet<Beam> {
+		let mut rows = vec![vec!['.'; 3]; 3];
+		rows[row][col] = splitter;
+		let input: String = rows
+			.iter()
+			.map(|row| row.iter().collect::<String>() + "\n")
+			.collect();
+		let mut board = parse_full(&input);
+		board.propagate(Beam {
+			direction,
+			row,
+			col,
+		})
+	}
+
+	#[test]
+	fn test_split_v_on_left_edge() {
+		assert_eq!(
+			split_at('|', 1, 0, Left),
+			HashSet::from([
+				Beam {
+					direction: Up,
+					row: 0,
+					col: 0
+				},
+				Beam {
+					direction: Down,
+					row: 2,
+					col: 0
+				},
+			])
+		);
+	}
+
+	#[test]
+	fn test_split_v_in_corners() {
+		for direction in [Left, Right] {
+			for (row, col, out_direction, out_row) in [
+				(0, 0, Down, 1),
+				(0, 2, Down, 1),
+				(2, 0, Up, 1),
+				(2, 2, Up, 1),
+			] {
+				assert_eq!(
+					split_at('|', row, col, direction),
+					HashSet::from([Beam {
+						direction: out_direction,
+						row: out_row,
+						col
+					}])
+				);
+			}
+		}
+	}
+
+	#[test]
+	fn test_split_h_in_corners() {
+		for direction in [Up, Down] {
+			for (row, col, out_direction, out_col) in [
+				(0, 0, Right, 1),
+				(0, 2, Left, 1),
+				(2, 0, Right, 1),
+				(2, 2, Left, 1),
+			] {
+				assert_eq!(
+					split_at('-', row, col, direction),
+					HashSet::from([Beam {
+						direction: out_direction,
+						row,
+						col: out_col
+					}])
+				);
+			}
+		}
+	}
+
+	#[test]
+	fn test_display_split_in_stable_order() {
+		let beam_in = Beam {
+			row: 1,
+			col: 1,
+			direction: Right,
+		};
+		for _ in 0..10 {
+			let mut board = parse_full("...\n.|.\n...\n");
+			let beams_out: Vec<String> = sorted(board.propagate(beam_in))
+				.iter()
+				.map(Beam::to_string)
+				.collect();
+			assert_eq!(beams_out.join(", "), "Up at (0, 1), Down at (2, 1)");
+		}
+	}
 }
 
 #[cfg(test)]
@@ -837,6 +1029,123 @@ mod test_trace {
 		assert_eq!(board.count_lit_tiles(), 3);
 	}
 
+	#[test]
+	fn test_beam_at() {
+		let board = parse_full("...\n...\n");
+		assert_eq!(
+			board.beam_at(1, 2, Left),
+			Some(Beam {
+				row: 1,
+				col: 2,
+				direction: Left,
+			})
+		);
+		assert_eq!(board.beam_at(2, 0, Up), None);
+		assert_eq!(board.beam_at(0, 3, Right), None);
+	}
+
+	#[test]
+	fn test_energize_sample() {
+		let mut board = parse_full(include_str!("../input_sample.txt"));
+		let beam = Beam {
+			row: 0,
+			col: 0,
+			direction: Direction::Right,
+		};
+		assert_eq!(board.energize(beam), 46);
+	}
+
+	#[test]
+	fn test_long_path() {
+		// Deep enough to overflow the stack if tracing recursed
+		let mut board = parse_full(&format!("{}\n", ".".repeat(200_000)));
+		board.trace(Beam {
+			row: 0,
+			col: 0,
+			direction: Direction::Right,
+		});
+		assert_eq!(board.count_lit_tiles(), 200_000);
+	}
+
+	#[test]
+	fn test_capped() {
+		let input = format!("{}\n", "|.-.".repeat(25)).repeat(100);
+		let mut board = parse_full(&input);
+		let beam = Beam {
+			row: 0,
+			col: 0,
+			direction: Direction::Right,
+		};
+		assert_eq!(
+			board.trace_capped(beam, Some(10)),
+			Err(TraceError::TooManyStates(10))
+		);
+		assert!(board.count_lit_tiles() <= 10);
+
+		board.clear_beams();
+		let nb_processed = board.trace_capped(beam, None).unwrap();
+		assert!(nb_processed > 10);
+		board.clear_beams();
+		assert_eq!(
+			board.trace_capped(beam, Some(nb_processed)),
+			Ok(nb_processed)
+		);
+	}
+
+	#[test]
+	fn test_capped_small_grid() {
+		let mut board = parse_full("...\n...\n");
+		let beam = Beam {
+			row: 0,
+			col: 0,
+			direction: Direction::Right,
+		};
+		assert_eq!(board.trace_capped(beam, Some(3)), Ok(3));
+		assert_eq!(board.count_lit_tiles(), 3);
+	}
+
+	#[test]
+	fn test_clear_and_retrace() {
+		let mut board = parse_full("...\n...\n");
+		board.trace(Beam {
+			row: 0,
+			col: 0,
+			direction: Direction::Right,
+		});
+		assert_eq!(board.count_lit_tiles(), 3);
+
+		board.clear_beams();
+		assert_eq!(board.count_lit_tiles(), 0);
+		assert_eq!(board, parse_full("...\n...\n"));
+
+		board.trace(Beam {
+			row: 1,
+			col: 1,
+			direction: Direction::Right,
+		});
+		assert_eq!(board.count_lit_tiles(), 2);
+	}
+
+	#[test]
+	fn test_energized_beams() {
+		let mut board = parse_full("...\n...\n");
+		assert_eq!(board.energized_beams(), HashSet::new());
+		board.trace(Beam {
+			row: 0,
+			col: 0,
+			direction: Direction::Right,
+		});
+
+		let expected = (0..3)
+			.map(|col| Beam {
+				row: 0,
+				col,
+				direction: Direction::Right,
+			})
+			.collect();
+		assert_eq!(board.energized_beams(), expected);
+	}
+
 	#[test]
 	fn test_cycle() {
 		let mut board = parse_full("-.|\n...\n|.-\n");
@@ -853,4 +1162,39 @@ mod test_trace {
 		// But not the central tile
 		assert!(!board.tiles[1][1].is_lit());
 	}
+
+	#[test]
+	fn test_cycle_beam_steps() {
+		let mut board = parse_full("-.|\n...\n|.-\n");
+		assert_eq!(board.total_beam_steps(), 0);
+		board.trace(Beam {
+			row: 0,
+			col: 0,
+			direction: Direction::Right,
+		});
+
+		// 8 lit tiles, one of them entered twice
+		assert_eq!(board.count_lit_tiles(), 8);
+		assert_eq!(board.total_beam_steps(), 9);
+	}
+
+	#[test]
+	fn test_cycle_by_direction() {
+		let mut board = parse_full("-.|\n...\n|.-\n");
+		assert_eq!(
+			board.count_lit_by_direction(),
+			HashMap::from([(Up, 0), (Down, 0), (Left, 0), (Right, 0)])
+		);
+		board.trace(Beam {
+			row: 0,
+			col: 0,
+			direction: Direction::Right,
+		});
+
+		// Top-left tile is entered twice: going right, then going up
+		assert_eq!(
+			board.count_lit_by_direction(),
+			HashMap::from([(Up, 2), (Down, 2), (Left, 2), (Right, 3)])
+		);
+	}
 }
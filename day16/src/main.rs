@@ -1,25 +1,33 @@
 #![allow(mixed_script_confusables)] // Using Γ to name a mirror type
 
-use std::io::{self, Read};
+use std::collections::HashSet;
+use std::env;
 
 use board::{Beam, Board, Direction};
+use common::{emit, has_flag, read_input, timed};
 use parse_input::parse_full;
 
 mod board;
 mod parse_input;
-mod pretty;
 
+/// Finds the starting beam that lights the most tiles.
+/// A beam already traversed by an earlier trace can only light a subset of that trace, so it's skipped.
 #[must_use]
 fn find_most_lit(initial_board: &Board, beams: &[Beam]) -> (Board, usize) {
 	let mut best_board = initial_board.clone();
 	let mut best_lit = 0;
+	let mut covered: HashSet<Beam> = HashSet::new();
+	let mut board = initial_board.clone();
 
 	for &beam in beams {
-		let mut board = initial_board.clone();
-		board.trace(beam);
-		let lit = board.count_lit_tiles();
+		if covered.contains(&beam) {
+			continue;
+		}
+		board.clear_beams();
+		let lit = board.energize(beam);
+		covered.extend(beams.iter().filter(|&&other| board.has_traversed(other)));
 		if lit > best_lit {
-			best_board = board;
+			best_board = board.clone();
 			best_lit = lit;
 		}
 	}
@@ -27,53 +35,95 @@ fn find_most_lit(initial_board: &Board, beams: &[Beam]) -> (Board, usize) {
 	(best_board, best_lit)
 }
 
+/// Finds the most tiles any starting beam lights, and every starting beam that lights that many.
+#[cfg(test)]
+#[must_use]
+fn all_best_starts(initial_board: &Board, beams: &[Beam]) -> (usize, Vec<Beam>) {
+	let mut best_lit = 0;
+	let mut best_beams = vec![];
+	let mut board = initial_board.clone();
+
+	for &beam in beams {
+		board.clear_beams();
+		let lit = board.energize(beam);
+		if lit > best_lit {
+			best_lit = lit;
+			best_beams.clear();
+		}
+		if lit == best_lit {
+			best_beams.push(beam);
+		}
+	}
+
+	(best_lit, best_beams)
+}
+
+/// Finds the starting beam that lights the most tiles, tracing beams on several threads.
+/// Ties go to the earliest beam, as in `find_most_lit`.
+#[cfg(feature = "parallel")]
+#[must_use]
+fn find_most_lit_parallel(initial_board: &Board, beams: &[Beam]) -> (Board, usize) {
+	common::map_chunks(beams, |chunk| find_most_lit(initial_board, chunk))
+		.into_iter()
+		.fold((initial_board.clone(), 0), |best, candidate| {
+			if candidate.1 > best.1 {
+				candidate
+			} else {
+				best
+			}
+		})
+}
+
 #[must_use]
-fn get_lit_from_top_left(input: &str) -> usize {
+fn get_lit_from_top_left(input: &str, reporting: bool) -> usize {
 	let mut board = parse_full(input);
 	let initial_beam = Beam {
 		row: 0,
 		col: 0,
 		direction: Direction::Right,
 	};
-	board.trace(initial_beam);
-	println!("{board}");
-	board.count_lit_tiles()
+	let lit = board.energize(initial_beam);
+	if reporting {
+		println!("{board}");
+	}
+	lit
 }
 
 #[must_use]
-fn get_most_lit(input: &str) -> usize {
+fn get_most_lit(input: &str, reporting: bool) -> usize {
 	let board = parse_full(input);
+	let beams = edge_beams(&board);
 
-	let right_beams = (0..board.nb_rows).map(|row| Beam {
-		row,
-		col: 0,
-		direction: Direction::Right,
-	});
-	let left_beams = (0..board.nb_rows).map(|row| Beam {
-		row,
-		col: board.nb_cols - 1,
-		direction: Direction::Left,
-	});
-	let down_beams = (0..board.nb_cols).map(|col| Beam {
-		row: 0,
-		col,
-		direction: Direction::Down,
-	});
-	let up_beams = (0..board.nb_cols).map(|col| Beam {
-		row: board.nb_rows - 1,
-		col,
-		direction: Direction::Up,
-	});
-
-	let beams: Vec<Beam> = up_beams
+	#[cfg(feature = "parallel")]
+	let (best_board, best_lit) = find_most_lit_parallel(&board, &beams);
+	#[cfg(not(feature = "parallel"))]
+	let (best_board, best_lit) = find_most_lit(&board, &beams);
+	if reporting {
+		println!("{best_board}");
+	}
+	best_lit
+}
+
+/// Lists every beam entering the board from an edge.
+#[must_use]
+fn edge_beams(board: &Board) -> Vec<Beam> {
+	let last_row = board.tiles.rows().saturating_sub(1);
+	let last_col = board.tiles.cols().saturating_sub(1);
+
+	let right_beams =
+		(0..board.tiles.rows()).filter_map(|row| board.beam_at(row, 0, Direction::Right));
+	let left_beams =
+		(0..board.tiles.rows()).filter_map(|row| board.beam_at(row, last_col, Direction::Left));
+	let down_beams =
+		(0..board.tiles.cols()).filter_map(|col| board.beam_at(0, col, Direction::Down));
+	let up_beams =
+		(0..board.tiles.cols()).filter_map(|col| board.beam_at(last_row, col, Direction::Up));
+
+	up_beams
 		.chain(down_beams)
 		.chain(left_beams)
 		.chain(right_beams)
-		.collect();
-
-	let (best_board, best_lit) = find_most_lit(&board, &beams);
-	println!("{best_board}");
-	best_lit
+		.collect()
 }
 
 #[cfg(test)]
@@ -83,17 +133,109 @@ mod test {
 
 	#[test]
 	fn test_sample() {
-		assert_eq!(get_lit_from_top_left(SAMPLE_INPUT), 46);
-		assert_eq!(get_most_lit(SAMPLE_INPUT), 51);
+		assert_eq!(get_lit_from_top_left(SAMPLE_INPUT, false), 46);
+		assert_eq!(get_most_lit(SAMPLE_INPUT, false), 51);
+	}
+
+	#[cfg(feature = "parallel")]
+	#[test]
+	fn test_sample_parallel() {
+		let board = parse_full(SAMPLE_INPUT);
+		let beams = edge_beams(&board);
+		let serial = find_most_lit(&board, &beams);
+		let parallel = find_most_lit_parallel(&board, &beams);
+		assert_eq!(parallel.1, 51);
+		assert_eq!(parallel, serial);
+	}
+
+	#[test]
+	fn test_all_best_starts() {
+		let board = parse_full("...\n.-.\n...\n");
+		let down = Beam {
+			row: 0,
+			col: 1,
+			direction: Direction::Down,
+		};
+		let up = Beam {
+			row: 2,
+			col: 1,
+			direction: Direction::Up,
+		};
+		let right = Beam {
+			row: 0,
+			col: 0,
+			direction: Direction::Right,
+		};
+		assert_eq!(
+			all_best_starts(&board, &[right, down, up]),
+			(4, vec![down, up])
+		);
+		assert_eq!(all_best_starts(&board, &[right]), (3, vec![right]));
+		assert_eq!(all_best_starts(&board, &[]), (0, vec![]));
+	}
+
+	#[test]
+	fn test_sample_all_best_starts() {
+		let board = parse_full(SAMPLE_INPUT);
+		let (best_lit, best_beams) = all_best_starts(&board, &edge_beams(&board));
+		assert_eq!(best_lit, 51);
+		assert!(best_beams.contains(&Beam {
+			row: 0,
+			col: 3,
+			direction: Direction::Down,
+		}));
+	}
+
+	#[test]
+	fn test_large_grid_matches_exhaustive() {
+		// Sample tiled 4 × 4, so plenty of edge beams share paths
+		let row_tiled: Vec<String> = SAMPLE_INPUT.lines().map(|line| line.repeat(4)).collect();
+		let input = (row_tiled.join("\n") + "\n").repeat(4);
+		let board = parse_full(&input);
+		assert_eq!((board.tiles.rows(), board.tiles.cols()), (40, 40));
+
+		let beams: Vec<Beam> = (0..board.tiles.rows())
+			.map(|row| Beam {
+				row,
+				col: 0,
+				direction: Direction::Right,
+			})
+			.chain((0..board.tiles.cols()).map(|col| Beam {
+				row: board.tiles.rows() - 1,
+				col,
+				direction: Direction::Up,
+			}))
+			.collect();
+
+		let exhaustive = beams
+			.iter()
+			.map(|&beam| {
+				let mut traced = board.clone();
+				traced.trace(beam);
+				traced.count_lit_tiles()
+			})
+			.max()
+			.unwrap();
+
+		let (_, best_lit) = find_most_lit(&board, &beams);
+		assert_eq!(best_lit, exhaustive);
 	}
 }
 
 fn main() {
-	let mut input = String::new();
-	io::stdin()
-		.read_to_string(&mut input)
-		.expect("Failed to read input");
+	let input = read_input(env::args().skip(1)).expect("Failed to read input");
 
-	println!("Part 1 — from top left: {}", get_lit_from_top_left(&input));
-	println!("Part 2 — most lit: {}", get_most_lit(&input));
+	let json = has_flag("--json");
+	let reporting = !json && !has_flag("--quiet");
+	emit(
+		(
+			"Part 1 — from top left",
+			timed("Part 1", || get_lit_from_top_left(&input, reporting)),
+		),
+		(
+			"Part 2 — most lit",
+			timed("Part 2", || get_most_lit(&input, reporting)),
+		),
+		json,
+	);
 }
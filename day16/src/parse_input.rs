@@ -1,17 +1,26 @@
+use common::{parse_tile_grid, GridError};
+
 use crate::board::{Board, Optics, Tile};
 
-/// Reads one tile.
-#[must_use]
-fn read_tile(c: char) -> Tile {
+/// Reads one tile, if it's `.`, `/`, `\`, `-`, `|` or `%`.
+fn read_tile(c: char) -> Option<Tile> {
 	let optics = match c {
 		'.' => Optics::Empty,
 		'/' => Optics::MirrorΓ,
 		'\\' => Optics::MirrorL,
 		'-' => Optics::SplitterH,
 		'|' => Optics::SplitterV,
-		_ => unreachable!(),
+		'%' => Optics::PartialMirrorL,
+		_ => return None,
 	};
-	Tile::from(optics)
+	Some(Tile::from(optics))
+}
+
+/// Parses the whole input.
+/// # Errors
+/// On an unknown tile, rows of different lengths, or an empty input.
+pub fn try_parse_full(input: &str) -> Result<Board, GridError> {
+	Ok(Board::from(parse_tile_grid(input, read_tile)?.into_rows()))
 }
 
 /// Parses the whole input.
@@ -19,11 +28,7 @@ fn read_tile(c: char) -> Tile {
 /// On any parse error.
 #[must_use]
 pub fn parse_full(input: &str) -> Board {
-	let tiles: Vec<Vec<Tile>> = input
-		.lines()
-		.map(|line| line.chars().map(read_tile).collect())
-		.collect();
-	Board::from(tiles)
+	try_parse_full(input).unwrap_or_else(|error| panic!("Parse error: {error}"))
 }
 
 #[cfg(test)]
@@ -36,8 +41,8 @@ mod test {
 		let input = ".\\/\n.|-\n";
 		let board = parse_full(input);
 
-		assert_eq!(board.nb_rows, 2);
-		assert_eq!(board.nb_cols, 3);
+		assert_eq!(board.tiles.rows(), 2);
+		assert_eq!(board.tiles.cols(), 3);
 
 		assert_eq!(board.tiles[0][0].optics, Empty);
 		assert_eq!(board.tiles[0][1].optics, MirrorL);
@@ -57,4 +62,40 @@ mod test {
 		]);
 		assert_eq!(parse_full(input), expected);
 	}
+
+	#[test]
+	fn test_partial_mirror() {
+		let board = parse_full("%.\n");
+		assert_eq!(board.tiles[0][0].optics, Optics::PartialMirrorL);
+	}
+
+	#[test]
+	fn test_invalid_tile() {
+		assert_eq!(
+			try_parse_full(".\\/\n.?-\n"),
+			Err(GridError::InvalidTile {
+				row: 1,
+				col: 1,
+				tile: '?'
+			})
+		);
+	}
+
+	#[test]
+	fn test_ragged_rows() {
+		assert_eq!(
+			try_parse_full("...\n.|\n..."),
+			Err(GridError::Ragged {
+				row: 1,
+				expected: 3,
+				found: 2
+			})
+		);
+	}
+
+	#[test]
+	fn test_empty() {
+		assert_eq!(try_parse_full(""), Err(GridError::Empty));
+		assert_eq!(try_parse_full("\n"), Err(GridError::Empty));
+	}
 }
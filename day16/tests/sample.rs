@@ -0,0 +1,14 @@
+use common::harness::run_sample;
+
+#[test]
+fn test_sample() {
+	assert_eq!(
+		run_sample(
+			env!("CARGO_BIN_EXE_day16"),
+			env!("CARGO_MANIFEST_DIR"),
+			"input_sample.txt",
+			&["--quiet"]
+		),
+		"Part 1 — from top left: 46\nPart 2 — most lit: 51\n"
+	);
+}
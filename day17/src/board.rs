@@ -1,15 +1,71 @@
 use crate::constraints::Constraints;
+use common::Grid;
 
-use self::Direction::{Down, Left, Right, Up};
-use std::{collections::HashSet, fmt};
+use self::Direction::{Down, DownLeft, DownRight, Left, Right, Up, UpLeft, UpRight};
+use std::{
+	collections::HashSet,
+	fmt,
+	hash::Hash,
+	ops::{Add, Mul},
+};
 
-/// Facing direction.
+/// Facing direction. Diagonals are only used if the constraints allow them.
 #[derive(Debug, Clone, Copy, Eq, Hash, Ord, PartialEq, PartialOrd)]
 pub enum Direction {
 	Up,
 	Down,
 	Left,
 	Right,
+	UpLeft,
+	UpRight,
+	DownLeft,
+	DownRight,
+}
+
+impl Direction {
+	pub const ALL: [Direction; 8] = [Up, Down, Left, Right, UpLeft, UpRight, DownLeft, DownRight];
+
+	/// Gets the (row, column) change from one step in this direction.
+	#[must_use]
+	fn delta(self) -> (isize, isize) {
+		match self {
+			Up => (-1, 0),
+			Down => (1, 0),
+			Left => (0, -1),
+			Right => (0, 1),
+			UpLeft => (-1, -1),
+			UpRight => (-1, 1),
+			DownLeft => (1, -1),
+			DownRight => (1, 1),
+		}
+	}
+
+	/// Checks if the direction is diagonal.
+	#[must_use]
+	pub fn is_diagonal(self) -> bool {
+		matches!(self, UpLeft | UpRight | DownLeft | DownRight)
+	}
+
+	/// Gets the direction going straight back the opposite way.
+	#[must_use]
+	fn opposite(self) -> Direction {
+		match self {
+			Up => Down,
+			Down => Up,
+			Left => Right,
+			Right => Left,
+			UpLeft => DownRight,
+			UpRight => DownLeft,
+			DownLeft => UpRight,
+			DownRight => UpLeft,
+		}
+	}
+
+	/// Checks if the direction goes straight back the opposite way.
+	#[must_use]
+	fn is_opposite(self, other: Direction) -> bool {
+		self.opposite() == other
+	}
 }
 
 /// A state the mover can be in.
@@ -22,86 +78,178 @@ pub struct State {
 	pub can_turn_in: usize,
 }
 
-/// A 2D array of tiles.
+impl State {
+	/// Checks if the mover may stop here: only once it's gone far enough to be allowed to turn.
+	#[must_use]
+	pub fn can_stop(&self) -> bool {
+		self.can_turn_in == 0
+	}
+}
+
+/// Cost of entering a tile, and of a whole path. `Default` is zero.
+pub trait Weight:
+	Copy
+	+ Default
+	+ Ord
+	+ Hash
+	+ fmt::Debug
+	+ fmt::Display
+	+ Add<Output = Self>
+	+ Mul<Output = Self>
+	+ TryFrom<usize>
+{
+}
+
+impl<T> Weight for T where
+	T: Copy
+		+ Default
+		+ Ord
+		+ Hash
+		+ fmt::Debug
+		+ fmt::Display
+		+ Add<Output = Self>
+		+ Mul<Output = Self>
+		+ TryFrom<usize>
+{
+}
+
+/// A 2D array of tiles weighing `W` each.
 #[derive(Debug, Clone, Eq, Hash, Ord, PartialEq, PartialOrd)]
-pub struct Board {
-	pub nb_rows: usize,
-	pub nb_cols: usize,
-	pub tiles: Vec<Vec<u32>>,
+pub struct WeightedBoard<W> {
+	pub tiles: Grid<W>,
 	pub constraints: Constraints,
 }
 
-impl Board {
+/// A board of single-digit heat losses.
+pub type Board = WeightedBoard<u32>;
+
+impl<W: Weight> WeightedBoard<W> {
 	/// Builds a board from an 2D array of tiles.
 	#[must_use]
-	pub fn from(tiles: Vec<Vec<u32>>, constraints: Constraints) -> Self {
+	pub fn from(tiles: Vec<Vec<W>>, constraints: Constraints) -> Self {
 		Self {
-			nb_rows: tiles.len(),
-			nb_cols: tiles.first().expect("Empty grid").len(),
-			tiles,
+			tiles: Grid::from(tiles),
 			constraints,
 		}
 	}
 
 	/// Gets the states reachable after one step, and their costs.
 	#[must_use]
-	pub fn get_neighbours(&self, start: &State) -> HashSet<(State, u32)> {
+	pub fn get_neighbours(&self, start: &State) -> HashSet<(State, W)> {
 		let mut neighbours = HashSet::new();
 
 		if start.can_turn_in == 0 {
-			let turn_directions = match start.facing {
-				Up | Down => [Left, Right],
-				Left | Right => [Up, Down],
-			};
+			// Turn any way but straight on or straight back
+			let turn_cost = self.turn_cost();
+			let turn_directions = Direction::ALL.into_iter().filter(|&new_facing| {
+				new_facing != start.facing && !new_facing.is_opposite(start.facing)
+			});
 			for new_facing in turn_directions {
-				self.add_neighbour_tile(
-					&mut neighbours,
-					start.row,
-					start.col,
-					new_facing,
-					self.constraints.max_straight_line - 1,
-					self.constraints.min_straight_line.saturating_sub(1),
-				);
+				if let Some((min, max)) = self.constraints.straight_line(new_facing) {
+					let turned = State {
+						facing: new_facing,
+						must_turn_in: max - 1,
+						can_turn_in: min.saturating_sub(1),
+						..*start
+					};
+					self.add_neighbour_tile(&mut neighbours, turned, turn_cost);
+				}
 			}
 		}
 
 		if start.must_turn_in > 0 {
-			self.add_neighbour_tile(
-				&mut neighbours,
-				start.row,
-				start.col,
-				start.facing,
-				start.must_turn_in - 1,
-				start.can_turn_in.saturating_sub(1),
-			);
+			let straight = State {
+				must_turn_in: start.must_turn_in - 1,
+				can_turn_in: start.can_turn_in.saturating_sub(1),
+				..*start
+			};
+			self.add_neighbour_tile(&mut neighbours, straight, W::default());
 		}
 
 		neighbours
 	}
 
-	/// Adds a neighbouring to a set if it exists.
+	/// Gets the states that reach this one in one step, and the cost of that step.
+	/// Walks the current line back, or, on its first tile, turns back onto any line long enough to turn from.
+	#[must_use]
+	pub fn get_predecessors(&self, end: &State) -> HashSet<(State, W)> {
+		let mut predecessors = HashSet::new();
+		let Some((min, max)) = self.constraints.straight_line(end.facing) else {
+			return predecessors;
+		};
+		let Some((row, col)) = self.get_neighbour_tile(end.row, end.col, end.facing.opposite())
+		else {
+			return predecessors;
+		};
+		let cost = self.tiles[end.row][end.col];
+
+		// Tiles gone in the current line, none if this is a start state
+		let run = max - end.must_turn_in;
+		if run == 0 {
+			return predecessors;
+		}
+
+		predecessors.insert((
+			State {
+				row,
+				col,
+				facing: end.facing,
+				must_turn_in: end.must_turn_in + 1,
+				can_turn_in: min.saturating_sub(run - 1),
+			},
+			cost,
+		));
+
+		if run == 1 {
+			let turned_cost = cost + self.turn_cost();
+			let turned_from = Direction::ALL.into_iter().filter(|&old_facing| {
+				old_facing != end.facing && !old_facing.is_opposite(end.facing)
+			});
+			for old_facing in turned_from {
+				if let Some((old_min, old_max)) = self.constraints.straight_line(old_facing) {
+					for old_run in old_min..=old_max {
+						predecessors.insert((
+							State {
+								row,
+								col,
+								facing: old_facing,
+								must_turn_in: old_max - old_run,
+								can_turn_in: 0,
+							},
+							turned_cost,
+						));
+					}
+				}
+			}
+		}
+
+		predecessors
+	}
+
+	/// Moves a state one tile along its facing and adds it to a set if that tile exists, costing the tile plus `extra_cost`.
 	fn add_neighbour_tile(
 		&self,
-		neighbours: &mut HashSet<(State, u32)>,
-		from_row: usize,
-		from_col: usize,
-		facing: Direction,
-		must_turn_in: usize,
-		can_turn_in: usize,
+		neighbours: &mut HashSet<(State, W)>,
+		moved: State,
+		extra_cost: W,
 	) {
-		if let Some((row, col)) = self.get_neighbour_tile(from_row, from_col, facing) {
-			let new_state = State {
-				row,
-				col,
-				facing,
-				must_turn_in,
-				can_turn_in,
-			};
-			let cost = self.tiles[row][col];
-			neighbours.insert((new_state, cost));
+		if let Some((row, col)) = self.get_neighbour_tile(moved.row, moved.col, moved.facing) {
+			let cost = self.tiles[row][col] + extra_cost;
+			neighbours.insert((State { row, col, ..moved }, cost));
 		}
 	}
 
+	/// Gets the extra cost of a turn, as a weight.
+	/// # Panics
+	/// If the turn cost doesn't fit in a weight.
+	#[must_use]
+	fn turn_cost(&self) -> W {
+		usize::try_from(self.constraints.turn_cost)
+			.ok()
+			.and_then(|turn_cost| W::try_from(turn_cost).ok())
+			.expect("Turn cost doesn't fit in a weight")
+	}
+
 	/// Gets the neighbouring tile in a direction if it exists.
 	#[must_use]
 	fn get_neighbour_tile(
@@ -110,49 +258,43 @@ impl Board {
 		col: usize,
 		direction: Direction,
 	) -> Option<(usize, usize)> {
-		match direction {
-			Up => {
-				if row > 0 {
-					Some((row - 1, col))
-				} else {
-					None
-				}
-			}
-			Down => {
-				if row < self.nb_rows - 1 {
-					Some((row + 1, col))
-				} else {
-					None
-				}
-			}
-			Left => {
-				if col > 0 {
-					Some((row, col - 1))
-				} else {
-					None
-				}
-			}
-			Right => {
-				if col < self.nb_cols - 1 {
-					Some((row, col + 1))
-				} else {
-					None
-				}
-			}
-		}
+		self.tiles.step(row, col, direction.delta())
 	}
 }
 
-impl fmt::Display for Board {
+impl<W: Weight> fmt::Display for WeightedBoard<W> {
 	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-		let mut res = writeln!(f);
-		for row in &self.tiles {
-			for tile in row {
-				_ = write!(f, "{tile}");
-			}
-			res = writeln!(f);
-		}
-		res
+		writeln!(f)?;
+		write!(f, "{}", self.tiles)
+	}
+}
+
+#[cfg(test)]
+mod test_can_stop {
+	use super::*;
+
+	#[test]
+	fn test_run_complete() {
+		let state = State {
+			row: 4,
+			col: 11,
+			facing: Down,
+			must_turn_in: 6,
+			can_turn_in: 0,
+		};
+		assert!(state.can_stop());
+	}
+
+	#[test]
+	fn test_run_incomplete() {
+		let state = State {
+			row: 4,
+			col: 11,
+			facing: Down,
+			must_turn_in: 8,
+			can_turn_in: 2,
+		};
+		assert!(!state.can_stop());
 	}
 }
 
@@ -169,6 +311,8 @@ mod test_get_neighbours {
 			Constraints {
 				min_straight_line: MIN_STRAIGHT_LINE,
 				max_straight_line: MAX_STRAIGHT_LINE,
+				diagonal_limits: None,
+				turn_cost: 0,
 			},
 		)
 	}
@@ -525,3 +669,50 @@ mod test_get_neighbours {
 		assert_eq!(next_states, expected);
 	}
 }
+
+#[cfg(test)]
+mod test_get_predecessors {
+	use super::*;
+
+	#[test]
+	fn test_mirrors_neighbours() {
+		let board = Board::from(
+			vec![vec![1, 2, 3, 4], vec![5, 6, 7, 8], vec![9, 1, 2, 3]],
+			Constraints::new(1, 2).unwrap(),
+		);
+		for row in 0..board.tiles.rows() {
+			for col in 0..board.tiles.cols() {
+				for facing in [Up, Down, Left, Right] {
+					for run in 1..=2 {
+						let start = State {
+							row,
+							col,
+							facing,
+							must_turn_in: 2 - run,
+							can_turn_in: 0,
+						};
+						for (neighbour, cost) in board.get_neighbours(&start) {
+							assert!(board.get_predecessors(&neighbour).contains(&(start, cost)));
+						}
+					}
+				}
+			}
+		}
+	}
+
+	#[test]
+	fn test_start_state() {
+		let board = Board::from(
+			vec![vec![1, 2], vec![3, 4]],
+			Constraints::new(1, 2).unwrap(),
+		);
+		let start = State {
+			row: 0,
+			col: 0,
+			facing: Right,
+			must_turn_in: 2,
+			can_turn_in: 1,
+		};
+		assert!(board.get_predecessors(&start).is_empty());
+	}
+}
@@ -1,16 +1,126 @@
+use crate::board::Direction;
+
 /// Constraints on the mover on the board
 #[derive(Debug, Clone, Copy, Eq, Hash, Ord, PartialEq, PartialOrd)]
 pub struct Constraints {
 	pub max_straight_line: usize,
 	pub min_straight_line: usize,
+	/// Min and max number of tiles in a diagonal line, if the mover may move diagonally.
+	pub diagonal_limits: Option<(usize, usize)>,
+	/// Extra cost each time the mover turns, on top of the tiles' own costs.
+	pub turn_cost: u32,
+}
+
+/// Reasons a pair of straight-line limits can't constrain a mover.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum ConstraintError {
+	/// The mover could never take a step.
+	ZeroMax,
+	/// The mover would have to turn before it's allowed to.
+	MinAboveMax,
+}
+
+impl Constraints {
+	/// Builds constraints from the min and max number of tiles in a straight line.
+	/// # Errors
+	/// If `max` is 0, or `min` is more than `max`.
+	pub fn new(min: usize, max: usize) -> Result<Self, ConstraintError> {
+		check_straight_line(min, max)?;
+		Ok(Self {
+			max_straight_line: max,
+			min_straight_line: min,
+			diagonal_limits: None,
+			turn_cost: 0,
+		})
+	}
+
+	/// Also lets the mover move diagonally, with its own min and max number of tiles in a line.
+	/// # Errors
+	/// If `max` is 0, or `min` is more than `max`.
+	pub fn with_diagonals(self, min: usize, max: usize) -> Result<Self, ConstraintError> {
+		check_straight_line(min, max)?;
+		Ok(Self {
+			diagonal_limits: Some((min, max)),
+			..self
+		})
+	}
+
+	/// Adds a fixed cost to every turn.
+	#[must_use]
+	pub fn with_turn_cost(self, turn_cost: u32) -> Self {
+		Self { turn_cost, ..self }
+	}
+
+	/// Gets the min and max number of tiles in a line facing a direction, if the mover may face it.
+	#[must_use]
+	pub fn straight_line(&self, facing: Direction) -> Option<(usize, usize)> {
+		if facing.is_diagonal() {
+			self.diagonal_limits
+		} else {
+			Some((self.min_straight_line, self.max_straight_line))
+		}
+	}
+}
+
+/// Checks that a min and max number of tiles in a line leave the mover somewhere to go.
+fn check_straight_line(min: usize, max: usize) -> Result<(), ConstraintError> {
+	if max == 0 {
+		Err(ConstraintError::ZeroMax)
+	} else if min > max {
+		Err(ConstraintError::MinAboveMax)
+	} else {
+		Ok(())
+	}
 }
 
 pub const CONSTRAINTS_PART_1: Constraints = Constraints {
 	max_straight_line: 3,
 	min_straight_line: 0,
+	diagonal_limits: None,
+	turn_cost: 0,
 };
 
 pub const CONSTRAINTS_PART_2: Constraints = Constraints {
 	max_straight_line: 10,
 	min_straight_line: 4,
+	diagonal_limits: None,
+	turn_cost: 0,
 };
+
+#[cfg(test)]
+mod test {
+	use super::*;
+
+	#[test]
+	fn test_valid() {
+		assert_eq!(Constraints::new(0, 3), Ok(CONSTRAINTS_PART_1));
+		assert_eq!(Constraints::new(4, 10), Ok(CONSTRAINTS_PART_2));
+		assert_eq!(
+			Constraints::new(2, 2),
+			Ok(Constraints {
+				max_straight_line: 2,
+				min_straight_line: 2,
+				diagonal_limits: None,
+				turn_cost: 0,
+			})
+		);
+	}
+
+	#[test]
+	fn test_invalid() {
+		assert_eq!(Constraints::new(0, 0), Err(ConstraintError::ZeroMax));
+		assert_eq!(Constraints::new(4, 3), Err(ConstraintError::MinAboveMax));
+	}
+
+	#[test]
+	fn test_diagonals() {
+		let constraints = CONSTRAINTS_PART_1.with_diagonals(1, 2).unwrap();
+		assert_eq!(constraints.straight_line(Direction::Up), Some((0, 3)));
+		assert_eq!(constraints.straight_line(Direction::DownLeft), Some((1, 2)));
+		assert_eq!(CONSTRAINTS_PART_1.straight_line(Direction::DownLeft), None);
+		assert_eq!(
+			CONSTRAINTS_PART_1.with_diagonals(3, 2),
+			Err(ConstraintError::MinAboveMax)
+		);
+	}
+}
@@ -0,0 +1,125 @@
+use board::Board;
+use constraints::{Constraints, CONSTRAINTS_PART_1, CONSTRAINTS_PART_2};
+use parse_input::parse_full;
+use pathfinding::find_path;
+
+pub mod board;
+pub mod constraints;
+pub mod parse_input;
+pub mod pathfinding;
+
+#[must_use]
+pub fn get_distance(input: &str, constraints: Constraints) -> u32 {
+	let tiles = parse_full(input);
+	let board = Board::from(tiles, constraints);
+	find_path(
+		&board,
+		(0, 0),
+		(board.tiles.rows() - 1, board.tiles.cols() - 1),
+	)
+}
+
+#[must_use]
+pub fn get_distance_part_1(input: &str) -> u32 {
+	get_distance(input, CONSTRAINTS_PART_1)
+}
+
+#[must_use]
+pub fn get_distance_part_2(input: &str) -> u32 {
+	get_distance(input, CONSTRAINTS_PART_2)
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+	use crate::board::Direction;
+	use crate::pathfinding::{
+		find_path_a_star, find_path_a_star_stats, find_path_bidirectional, find_path_from,
+		find_path_stats, find_path_with_route,
+	};
+	const SAMPLE_INPUT: &str = include_str!("../input_sample.txt");
+	const SAMPLE_INPUT_PART_2: &str = include_str!("../input_sample_part2.txt");
+
+	#[test]
+	fn test_sample() {
+		assert_eq!(get_distance_part_1(SAMPLE_INPUT), 102);
+		assert_eq!(get_distance_part_2(SAMPLE_INPUT), 94);
+	}
+
+	#[test]
+	fn test_sample_a_star() {
+		for (constraints, expected) in [(CONSTRAINTS_PART_1, 102), (CONSTRAINTS_PART_2, 94)] {
+			let board = Board::from(parse_full(SAMPLE_INPUT), constraints);
+			let goal = (board.tiles.rows() - 1, board.tiles.cols() - 1);
+			assert_eq!(find_path_a_star(&board, (0, 0), goal), expected);
+		}
+	}
+
+	#[test]
+	fn test_sample_bidirectional() {
+		for (constraints, expected) in [(CONSTRAINTS_PART_1, 102), (CONSTRAINTS_PART_2, 94)] {
+			let board = Board::from(parse_full(SAMPLE_INPUT), constraints);
+			let goal = (board.tiles.rows() - 1, board.tiles.cols() - 1);
+			assert_eq!(find_path(&board, (0, 0), goal), expected);
+			assert_eq!(find_path_bidirectional(&board, (0, 0), goal), expected);
+		}
+		let board = Board::from(parse_full(SAMPLE_INPUT_PART_2), CONSTRAINTS_PART_2);
+		let goal = (board.tiles.rows() - 1, board.tiles.cols() - 1);
+		assert_eq!(find_path_bidirectional(&board, (0, 0), goal), 71);
+	}
+
+	#[test]
+	fn test_sample_a_star_expands_less() {
+		for constraints in [CONSTRAINTS_PART_1, CONSTRAINTS_PART_2] {
+			let board = Board::from(parse_full(SAMPLE_INPUT), constraints);
+			let goal = (board.tiles.rows() - 1, board.tiles.cols() - 1);
+			let (dijkstra_cost, dijkstra_expanded) = find_path_stats(&board, (0, 0), goal);
+			let (a_star_cost, a_star_expanded) = find_path_a_star_stats(&board, (0, 0), goal);
+			assert_eq!(a_star_cost, dijkstra_cost);
+			assert!(a_star_expanded <= dijkstra_expanded);
+		}
+	}
+
+	#[test]
+	fn test_sample_restricted_facings() {
+		let board = Board::from(parse_full(SAMPLE_INPUT), CONSTRAINTS_PART_1);
+		let goal = (board.tiles.rows() - 1, board.tiles.cols() - 1);
+		let facings = [Direction::Right, Direction::Down];
+		assert_eq!(find_path_from(&board, (0, 0), goal, &facings), 102);
+	}
+
+	#[test]
+	fn test_sample_route() {
+		let board = Board::from(parse_full(SAMPLE_INPUT), CONSTRAINTS_PART_1);
+		let goal = (board.tiles.rows() - 1, board.tiles.cols() - 1);
+		let (cost, route) = find_path_with_route(&board, (0, 0), goal);
+		assert_eq!(cost, 102);
+		// Equally cheap routes can differ in length, but need at least one step per row and column crossed
+		assert!(route.len() > goal.0 + goal.1);
+		assert_eq!((route.len() - 1) % 2, (goal.0 + goal.1) % 2);
+		assert_eq!(route.first(), Some(&(0, 0)));
+		assert_eq!(route.last(), Some(&goal));
+		for pair in route.windows(2) {
+			let ((row_a, col_a), (row_b, col_b)) = (pair[0], pair[1]);
+			assert_eq!(row_a.abs_diff(row_b) + col_a.abs_diff(col_b), 1);
+		}
+		// The start tile isn't paid for
+		let route_cost: u32 = route[1..]
+			.iter()
+			.map(|&(row, col)| board.tiles[row][col])
+			.sum();
+		assert_eq!(route_cost, 102);
+	}
+
+	#[test]
+	fn test_force_bad_turn() {
+		assert_eq!(get_distance_part_2(SAMPLE_INPUT_PART_2), 71);
+	}
+
+	#[test]
+	fn test_force_bad_turn_needs_full_run() {
+		// Without a minimum run, the mover can turn and stop after shorter runs
+		let no_min = Constraints::new(0, 10).unwrap();
+		assert!(get_distance(SAMPLE_INPUT_PART_2, no_min) < 71);
+	}
+}
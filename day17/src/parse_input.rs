@@ -1,16 +1,23 @@
+use common::{parse_tile_grid, GridError};
+
+/// Reads one tile, if it's a digit.
+fn read_tile(ch: char) -> Option<u32> {
+	ch.to_digit(10)
+}
+
+/// Parses the whole input.
+/// # Errors
+/// On a non-digit, rows of different lengths, or an empty input.
+pub fn try_parse_full(input: &str) -> Result<Vec<Vec<u32>>, GridError> {
+	Ok(parse_tile_grid(input, read_tile)?.into_rows())
+}
+
 /// Parses the whole input.
 /// # Panics
 /// On any parse error.
 #[must_use]
 pub fn parse_full(input: &str) -> Vec<Vec<u32>> {
-	input
-		.lines()
-		.map(|line| {
-			line.chars()
-				.map(|ch| ch.to_digit(10).expect("Not a digit"))
-				.collect()
-		})
-		.collect()
+	try_parse_full(input).unwrap_or_else(|error| panic!("Parse error: {error}"))
 }
 
 #[cfg(test)]
@@ -23,4 +30,34 @@ mod test {
 		let expected = vec![vec![1, 2, 3], vec![4, 5, 6]];
 		assert_eq!(parse_full(input), expected);
 	}
+
+	#[test]
+	fn test_invalid_tile() {
+		assert_eq!(
+			try_parse_full("123\n4a6\n"),
+			Err(GridError::InvalidTile {
+				row: 1,
+				col: 1,
+				tile: 'a'
+			})
+		);
+	}
+
+	#[test]
+	fn test_ragged_rows() {
+		assert_eq!(
+			try_parse_full("123\n45\n789\n"),
+			Err(GridError::Ragged {
+				row: 1,
+				expected: 3,
+				found: 2
+			})
+		);
+	}
+
+	#[test]
+	fn test_empty() {
+		assert_eq!(try_parse_full(""), Err(GridError::Empty));
+		assert_eq!(try_parse_full("\n"), Err(GridError::Empty));
+	}
 }
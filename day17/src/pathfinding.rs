@@ -1,28 +1,54 @@
-use std::{cmp::Reverse, collections::HashSet};
+use std::{
+	cmp::Reverse,
+	collections::{HashMap, HashSet},
+};
 
 use crate::{
-	board::{
-		Board,
-		Direction::{Down, Left, Right, Up},
-		State,
-	},
+	board::{Direction, State, Weight, WeightedBoard},
 	constraints::Constraints,
 };
 use priority_queue::PriorityQueue;
 
 /// A step on a path through the board.
 #[derive(Debug, Clone, Copy, Eq, Hash, Ord, PartialEq, PartialOrd)]
-struct PathStep {
+struct PathStep<W> {
 	pub state: State,
-	pub cost_to: u32,
-	// Could save previous step if we want to draw the path
+	pub cost_to: W,
+	/// State this step was reached from, if any.
+	pub previous: Option<State>,
+}
+
+/// Outcome of a search that reached the goal.
+struct SearchResult<W> {
+	/// Cost of the least costly path.
+	cost: W,
+	/// State the path ends in.
+	end: State,
+	/// The state each explored state was first reached from.
+	came_from: HashMap<State, State>,
+	/// How many states were expanded.
+	nb_expanded: usize,
+}
+
+impl<W> SearchResult<W> {
+	/// Lists the positions on the path, from start to goal.
+	fn route(&self) -> Vec<(usize, usize)> {
+		let mut route = vec![(self.end.row, self.end.col)];
+		let mut state = self.end;
+		while let Some(&previous) = self.came_from.get(&state) {
+			route.push((previous.row, previous.col));
+			state = previous;
+		}
+		route.reverse();
+		route
+	}
 }
 
-struct PathQueue {
-	queue: PriorityQueue<PathStep, Reverse<u32>>,
+struct PathQueue<W: Weight> {
+	queue: PriorityQueue<PathStep<W>, Reverse<W>>,
 }
 
-impl PathQueue {
+impl<W: Weight> PathQueue<W> {
 	/// Creates an empty queue.
 	#[must_use]
 	pub fn new() -> Self {
@@ -31,72 +57,392 @@ impl PathQueue {
 		}
 	}
 
-	/// Adds a step to the queue.
-	pub fn push(&mut self, step: PathStep) {
-		self.queue.push(step, Reverse(step.cost_to));
+	/// Adds a step to the queue, to be popped in order of its estimated total cost.
+	pub fn push(&mut self, step: PathStep<W>, estimate_to_goal: W) {
+		self.queue
+			.push(step, Reverse(step.cost_to + estimate_to_goal));
 	}
 
-	/// Pops the least-costly step from the queue.
+	/// Pops the least-costly step from the queue, if any.
 	#[must_use]
-	pub fn pop(&mut self) -> PathStep {
-		let (step, _) = self.queue.pop().expect("No path exists!");
-		step
+	pub fn pop(&mut self) -> Option<PathStep<W>> {
+		self.queue.pop().map(|(step, _)| step)
 	}
 }
 
+/// One side of a bidirectional search.
+struct Frontier<W: Weight> {
+	queue: PriorityQueue<State, Reverse<W>>,
+	/// Least cost found so far to each state reached.
+	costs: HashMap<State, W>,
+}
+
+impl<W: Weight> Frontier<W> {
+	/// Creates an empty frontier.
+	#[must_use]
+	fn new() -> Self {
+		Self {
+			queue: PriorityQueue::new(),
+			costs: HashMap::new(),
+		}
+	}
+
+	/// Records reaching a state at a cost, unless it was already reached more cheaply.
+	fn reach(&mut self, state: State, cost: W) {
+		if self
+			.costs
+			.get(&state)
+			.is_some_and(|&old_cost| old_cost <= cost)
+		{
+			return;
+		}
+		self.costs.insert(state, cost);
+		self.queue.push_increase(state, Reverse(cost));
+	}
+
+	/// Gets the cost of the least costly state left to expand, if any.
+	#[must_use]
+	fn min_cost(&self) -> Option<W> {
+		self.queue.peek().map(|(_, &Reverse(cost))| cost)
+	}
+
+	/// Pops the least costly state left to expand, if any.
+	#[must_use]
+	fn pop(&mut self) -> Option<(State, W)> {
+		self.queue.pop().map(|(state, Reverse(cost))| (state, cost))
+	}
+}
+
+/// The goal can't be reached from the start under the board's constraints.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub struct NoPath;
+
+/// Finds the least costly path from start to goal on a board, and returns its cost.
+/// # Panics
+/// If there's no path.
+#[must_use]
+pub fn find_path<W: Weight>(
+	board: &WeightedBoard<W>,
+	start: (usize, usize),
+	goal: (usize, usize),
+) -> W {
+	try_find_path(board, start, goal).expect("No path exists!")
+}
+
 /// Finds the least costly path from start to goal on a board, and returns its cost.
-pub fn find_path(board: &Board, start: (usize, usize), goal: (usize, usize)) -> u32 {
+/// # Errors
+/// If there's no path.
+pub fn try_find_path<W: Weight>(
+	board: &WeightedBoard<W>,
+	start: (usize, usize),
+	goal: (usize, usize),
+) -> Result<W, NoPath> {
+	// Dijkstra
+	Ok(search(board, start, goal, |_, _| W::default())?.cost)
+}
+
+/// Finds the least cost from start to every reachable state on a board, starting in any direction.
+#[must_use]
+pub fn cost_field<W: Weight>(board: &WeightedBoard<W>, start: (usize, usize)) -> HashMap<State, W> {
+	let mut open = PathQueue::new();
+	let mut costs = HashMap::new();
+
+	for step in get_start_steps(start, &Direction::ALL, &board.constraints) {
+		open.push(step, W::default());
+	}
+
+	while let Some(step) = open.pop() {
+		if costs.contains_key(&step.state) {
+			continue;
+		}
+		costs.insert(step.state, step.cost_to);
+
+		for (neighbour, new_cost) in board.get_neighbours(&step.state) {
+			if !costs.contains_key(&neighbour) {
+				open.push(
+					PathStep {
+						state: neighbour,
+						cost_to: step.cost_to + new_cost,
+						previous: Some(step.state),
+					},
+					W::default(),
+				);
+			}
+		}
+	}
+
+	costs
+}
+
+/// Finds the least costly path from start to goal on a board, starting facing one of `facings`, and returns its cost.
+/// # Panics
+/// If there's no path.
+#[must_use]
+pub fn find_path_from<W: Weight>(
+	board: &WeightedBoard<W>,
+	start: (usize, usize),
+	goal: (usize, usize),
+	facings: &[Direction],
+) -> W {
+	search_from(board, start, facings, goal, |_, _| W::default())
+		.expect("No path exists!")
+		.cost
+}
+
+/// Finds the least costly path from start to goal on a board.
+/// Returns its cost and the positions along it, from start to goal.
+/// # Panics
+/// If there's no path.
+#[must_use]
+pub fn find_path_with_route<W: Weight>(
+	board: &WeightedBoard<W>,
+	start: (usize, usize),
+	goal: (usize, usize),
+) -> (W, Vec<(usize, usize)>) {
+	let result = search(board, start, goal, |_, _| W::default()).expect("No path exists!");
+	(result.cost, result.route())
+}
+
+/// Finds the least costly path from start to goal on a board.
+/// Returns its cost and how many states were expanded to find it.
+/// # Panics
+/// If there's no path.
+#[must_use]
+pub fn find_path_stats<W: Weight>(
+	board: &WeightedBoard<W>,
+	start: (usize, usize),
+	goal: (usize, usize),
+) -> (W, usize) {
+	let result = search(board, start, goal, |_, _| W::default()).expect("No path exists!");
+	(result.cost, result.nb_expanded)
+}
+
+/// Finds the least costly path from start to goal on a board with A*.
+/// Returns its cost and how many states were expanded to find it.
+/// # Panics
+/// If there's no path.
+#[must_use]
+pub fn find_path_a_star_stats<W: Weight>(
+	board: &WeightedBoard<W>,
+	start: (usize, usize),
+	goal: (usize, usize),
+) -> (W, usize) {
+	let result =
+		search(board, start, goal, manhattan_estimate(board, goal)).expect("No path exists!");
+	(result.cost, result.nb_expanded)
+}
+
+/// Finds the least costly path from start to goal on a board with A*, and returns its cost.
+/// The heuristic is the Manhattan distance to the goal times the cheapest tile cost, which never overestimates.
+/// # Panics
+/// If there's no path.
+#[must_use]
+pub fn find_path_a_star<W: Weight>(
+	board: &WeightedBoard<W>,
+	start: (usize, usize),
+	goal: (usize, usize),
+) -> W {
+	search(board, start, goal, manhattan_estimate(board, goal))
+		.expect("No path exists!")
+		.cost
+}
+
+/// Finds the least costly path from start to goal on a board, and returns its cost.
+/// Searches forwards from the start and backwards from the goal at the same time, and stops once they meet
+/// and neither side can find anything cheaper.
+/// # Panics
+/// If there's no path.
+#[must_use]
+pub fn find_path_bidirectional<W: Weight>(
+	board: &WeightedBoard<W>,
+	start: (usize, usize),
+	goal: (usize, usize),
+) -> W {
+	let mut forward = Frontier::new();
+	for step in get_start_steps::<W>(start, &Direction::ALL, &board.constraints) {
+		forward.reach(step.state, W::default());
+	}
+	let mut backward = Frontier::new();
+	for state in get_goal_states(goal, &board.constraints) {
+		backward.reach(state, W::default());
+	}
+
+	// Start and goal states can already overlap
+	let mut best = forward
+		.costs
+		.iter()
+		.filter_map(|(state, &cost)| Some(cost + *backward.costs.get(state)?))
+		.min();
+
+	while let (Some(forward_min), Some(backward_min)) = (forward.min_cost(), backward.min_cost()) {
+		if best.is_some_and(|best| forward_min + backward_min >= best) {
+			break;
+		}
+
+		let is_forward = forward_min <= backward_min;
+		let (frontier, other) = if is_forward {
+			(&mut forward, &backward)
+		} else {
+			(&mut backward, &forward)
+		};
+		let Some((state, cost)) = frontier.pop() else {
+			break;
+		};
+		let next_states = if is_forward {
+			board.get_neighbours(&state)
+		} else {
+			board.get_predecessors(&state)
+		};
+		for (next_state, step_cost) in next_states {
+			let cost_to = cost + step_cost;
+			if let Some(&other_cost) = other.costs.get(&next_state) {
+				let through = cost_to + other_cost;
+				best = Some(best.map_or(through, |best| best.min(through)));
+			}
+			frontier.reach(next_state, cost_to);
+		}
+	}
+
+	best.expect("No path exists!")
+}
+
+/// Estimates the cost to the goal as the Manhattan distance times the cheapest tile cost.
+/// Counts a diagonal step as one step if the mover can move diagonally.
+fn manhattan_estimate<W: Weight>(
+	board: &WeightedBoard<W>,
+	goal: (usize, usize),
+) -> impl Fn(usize, usize) -> W {
+	let min_cost = board.tiles.iter().copied().min().unwrap_or_default();
+	let diagonals = board.constraints.diagonal_limits.is_some();
+	move |row, col| {
+		let (row_distance, col_distance) = (row.abs_diff(goal.0), col.abs_diff(goal.1));
+		let distance = if diagonals {
+			row_distance.max(col_distance)
+		} else {
+			row_distance + col_distance
+		};
+		let Ok(distance) = W::try_from(distance) else {
+			panic!("Board too big");
+		};
+		distance * min_cost
+	}
+}
+
+/// Explores steps in order of cost so far plus estimated cost from their tile to the goal.
+/// Starts in any direction.
+fn search<W: Weight>(
+	board: &WeightedBoard<W>,
+	start: (usize, usize),
+	goal: (usize, usize),
+	estimate_to_goal: impl Fn(usize, usize) -> W,
+) -> Result<SearchResult<W>, NoPath> {
+	search_from(board, start, &Direction::ALL, goal, estimate_to_goal)
+}
+
+/// Explores steps in order of cost so far plus estimated cost from their tile to the goal.
+/// Starts facing any of the given directions.
+fn search_from<W: Weight>(
+	board: &WeightedBoard<W>,
+	start: (usize, usize),
+	facings: &[Direction],
+	goal: (usize, usize),
+	estimate_to_goal: impl Fn(usize, usize) -> W,
+) -> Result<SearchResult<W>, NoPath> {
 	let mut open = PathQueue::new();
 	let mut closed = HashSet::<State>::new();
+	let mut came_from = HashMap::new();
+	let mut nb_expanded = 0;
 
-	// Start in any direction
-	for step in get_start_steps(start, &board.constraints) {
-		open.push(step);
+	for step in get_start_steps(start, facings, &board.constraints) {
+		open.push(step, estimate_to_goal(start.0, start.1));
 	}
 
-	// Dijkstra
-	loop {
-		let step = open.pop();
-		// If you can't turn, you also can't stop.
-		if (step.state.row, step.state.col) == goal && step.state.can_turn_in == 0 {
-			return step.cost_to;
+	while let Some(step) = open.pop() {
+		// Already reached more cheaply
+		if !closed.insert(step.state) {
+			continue;
+		}
+		nb_expanded += 1;
+		if let Some(previous) = step.previous {
+			came_from.insert(step.state, previous);
+		}
+		if (step.state.row, step.state.col) == goal && step.state.can_stop() {
+			return Ok(SearchResult {
+				cost: step.cost_to,
+				end: step.state,
+				came_from,
+				nb_expanded,
+			});
 		}
-		closed.insert(step.state);
 
 		let neighbours = board.get_neighbours(&step.state);
 		for (neighbour, new_cost) in neighbours {
 			if !closed.contains(&neighbour) {
 				let cost_to = step.cost_to + new_cost;
-				open.push(PathStep {
-					state: neighbour,
-					cost_to,
-				});
+				open.push(
+					PathStep {
+						state: neighbour,
+						cost_to,
+						previous: Some(step.state),
+					},
+					estimate_to_goal(neighbour.row, neighbour.col),
+				);
 			}
 		}
 	}
+
+	Err(NoPath)
 }
 
-fn get_start_steps(
+fn get_start_steps<'a, W: Weight>(
 	(row, col): (usize, usize),
-	constraints: &Constraints,
-) -> impl Iterator<Item = PathStep> {
-	[Up, Down, Left, Right]
-		.map(|facing| PathStep {
+	facings: &'a [Direction],
+	constraints: &'a Constraints,
+) -> impl Iterator<Item = PathStep<W>> + 'a {
+	facings.iter().filter_map(move |&facing| {
+		let (min, max) = constraints.straight_line(facing)?;
+		Some(PathStep {
 			state: State {
 				row,
 				col,
 				facing,
-				must_turn_in: constraints.max_straight_line,
-				can_turn_in: constraints.min_straight_line,
+				must_turn_in: max,
+				can_turn_in: min,
 			},
-			cost_to: 0,
+			cost_to: W::default(),
+			previous: None,
+		})
+	})
+}
+
+/// Gets the states the mover may stop in at the goal, facing any direction allowed by the constraints.
+fn get_goal_states(
+	(row, col): (usize, usize),
+	constraints: &Constraints,
+) -> impl Iterator<Item = State> + '_ {
+	Direction::ALL.into_iter().flat_map(move |facing| {
+		let limits = constraints.straight_line(facing);
+		limits.into_iter().flat_map(move |(min, max)| {
+			(min..=max).map(move |run| State {
+				row,
+				col,
+				facing,
+				must_turn_in: max - run,
+				can_turn_in: 0,
+			})
 		})
-		.into_iter()
+	})
 }
 
 #[cfg(test)]
 mod test_find_path {
-	use crate::constraints::CONSTRAINTS_PART_1;
+	use crate::{
+		board::{
+			Board,
+			Direction::{Down, Right, Up},
+		},
+		constraints::CONSTRAINTS_PART_1,
+	};
 
 	use super::*;
 
@@ -107,6 +453,40 @@ mod test_find_path {
 		assert_eq!(distance, 2);
 	}
 
+	#[test]
+	fn test_diagonal_shortcut() {
+		let tiles = vec![vec![1, 9, 9], vec![9, 1, 9], vec![9, 9, 1]];
+		let orthogonal = Board::from(tiles.clone(), CONSTRAINTS_PART_1);
+		let diagonal = Board::from(tiles, CONSTRAINTS_PART_1.with_diagonals(0, 3).unwrap());
+		assert_eq!(find_path(&orthogonal, (0, 0), (2, 2)), 9 + 1 + 9 + 1);
+		assert_eq!(find_path(&diagonal, (0, 0), (2, 2)), 2);
+		assert_eq!(find_path_a_star(&diagonal, (0, 0), (2, 2)), 2);
+	}
+
+	#[test]
+	fn test_tiny_cost_field() {
+		let board = Board::from(vec![vec![1, 1], vec![1, 1]], CONSTRAINTS_PART_1);
+		let costs = cost_field(&board, (0, 0));
+		let state = |row, col, facing, must_turn_in| State {
+			row,
+			col,
+			facing,
+			must_turn_in,
+			can_turn_in: 0,
+		};
+		assert_eq!(costs.get(&state(0, 0, Up, 3)), Some(&0));
+		assert_eq!(costs.get(&state(0, 1, Right, 2)), Some(&1));
+		assert_eq!(costs.get(&state(1, 1, Down, 2)), Some(&2));
+		assert_eq!(costs.get(&state(1, 1, Down, 0)), None);
+
+		let goal_cost = costs
+			.iter()
+			.filter(|(state, _)| (state.row, state.col) == (1, 1) && state.can_stop())
+			.map(|(_, &cost)| cost)
+			.min();
+		assert_eq!(goal_cost, Some(find_path(&board, (0, 0), (1, 1))));
+	}
+
 	#[test]
 	fn test_twisty() {
 		let board = Board::from(
@@ -117,10 +497,61 @@ mod test_find_path {
 		assert_eq!(distance, 4);
 	}
 
+	#[test]
+	fn test_twisty_turn_cost() {
+		let tiles = vec![vec![1, 2, 2], vec![1, 1, 2], vec![2, 1, 1]];
+		// Zigzagging costs 4 + 3 turns, down then right costs 5 + 1 turn.
+		for (turn_cost, expected) in [(0, 4), (1, 6), (10, 15)] {
+			let board = Board::from(tiles.clone(), CONSTRAINTS_PART_1.with_turn_cost(turn_cost));
+			assert_eq!(find_path(&board, (0, 0), (2, 2)), expected);
+			assert_eq!(find_path_a_star(&board, (0, 0), (2, 2)), expected);
+			assert_eq!(find_path_bidirectional(&board, (0, 0), (2, 2)), expected);
+		}
+	}
+
+	#[test]
+	fn test_twisty_a_star() {
+		let board = Board::from(
+			vec![vec![1, 2, 2], vec![1, 1, 2], vec![2, 1, 1]],
+			CONSTRAINTS_PART_1,
+		);
+		assert_eq!(
+			find_path_a_star(&board, (0, 0), (2, 2)),
+			find_path(&board, (0, 0), (2, 2))
+		);
+	}
+
 	#[test]
 	fn test_force_turn() {
 		let board = Board::from(vec![vec![1; 5], vec![6; 5]], CONSTRAINTS_PART_1);
 		let distance = find_path(&board, (0, 0), (0, 4));
 		assert_eq!(distance, 4 + 2 * 6);
 	}
+
+	#[test]
+	fn test_u64_weights() {
+		let heavy = u64::from(u32::MAX) - 1;
+		let board = WeightedBoard::from(vec![vec![heavy; 3]; 3], CONSTRAINTS_PART_1);
+		assert_eq!(find_path(&board, (0, 0), (2, 2)), 4 * heavy);
+		assert_eq!(find_path_a_star(&board, (0, 0), (2, 2)), 4 * heavy);
+	}
+
+	#[test]
+	fn test_no_path() {
+		let constraints = Constraints {
+			max_straight_line: 10,
+			min_straight_line: 6,
+			diagonal_limits: None,
+			turn_cost: 0,
+		};
+		let board = Board::from(vec![vec![1; 5]], constraints);
+		assert_eq!(try_find_path(&board, (0, 0), (0, 4)), Err(NoPath));
+		assert_eq!(try_find_path(&board, (0, 0), (0, 0)), Err(NoPath));
+	}
+
+	#[test]
+	fn test_try_find_path() {
+		let board = Board::from(vec![vec![1, 1], vec![1, 1]], CONSTRAINTS_PART_1);
+		assert_eq!(try_find_path(&board, (0, 0), (1, 1)), Ok(2));
+	}
 }
@@ -0,0 +1,27 @@
+use common::harness::run_sample;
+
+#[test]
+fn test_sample() {
+	assert_eq!(
+		run_sample(
+			env!("CARGO_BIN_EXE_day17"),
+			env!("CARGO_MANIFEST_DIR"),
+			"input_sample.txt",
+			&[]
+		),
+		"Part 1 — straight line max 3, no min: 102\nPart 2 — straight line max 10, min 4: 94\n"
+	);
+}
+
+#[test]
+fn test_sample_part2() {
+	assert_eq!(
+		run_sample(
+			env!("CARGO_BIN_EXE_day17"),
+			env!("CARGO_MANIFEST_DIR"),
+			"input_sample_part2.txt",
+			&[]
+		),
+		"Part 1 — straight line max 3, no min: 59\nPart 2 — straight line max 10, min 4: 71\n"
+	);
+}
@@ -0,0 +1,62 @@
+use std::env;
+
+use common::{
+	day::{solver, Solver},
+	emit, has_flag, read_input, timed,
+};
+use day01::Day01;
+use day06::Day06;
+use day09::Day09;
+
+/// Gets the solver for a day, if that day is available as a library.
+#[must_use]
+fn get_solver(day: usize) -> Option<Solver> {
+	match day {
+		1 => Some(solver::<Day01>()),
+		6 => Some(solver::<Day06>()),
+		9 => Some(solver::<Day09>()),
+		_ => None,
+	}
+}
+
+fn main() {
+	let day: usize = env::args()
+		.nth(1)
+		.and_then(|arg| arg.parse().ok())
+		.expect("Usage: runner <day> [input file]");
+	let (part1, part2) = get_solver(day).expect("No such day");
+	let input = read_input(env::args().skip(2)).expect("Failed to read input");
+
+	let json = has_flag("--json");
+	emit(
+		("Part 1", timed("Part 1", || part1(&input))),
+		("Part 2", timed("Part 2", || part2(&input))),
+		json,
+	);
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+	use day01::get_total;
+
+	#[test]
+	fn test_import_day01() {
+		let sample = include_str!("../../day01/input_sample_a.txt");
+		assert_eq!(get_total(sample, false), 142);
+	}
+
+	#[test]
+	fn test_get_solver() {
+		let (part1, part2) = get_solver(6).expect("Day 6 is a library");
+		let sample = include_str!("../../day06/input_sample.txt");
+		assert_eq!(part1(sample), "288");
+		assert_eq!(part2(sample), "71503");
+	}
+
+	#[test]
+	fn test_get_solver_unknown() {
+		assert!(get_solver(0).is_none());
+		assert!(get_solver(2).is_none());
+	}
+}